@@ -1,41 +1,64 @@
+use advent_of_code_2024::{DynSolution, DAYS};
 use criterion::{criterion_group, criterion_main, Criterion};
-use paste::paste;
 
-/// Get input for a single day
-macro_rules! get_day_input {
-    ($day_num:literal) => {
-        include_str!(concat!("../inputs/day", $day_num, ".txt"))
-    };
+/// Get input for a single day, or the reason why it can't be benchmarked
+fn get_day_input(solution: &dyn DynSolution) -> Result<String, String> {
+    let path = format!(
+        "{}/inputs/day{}.txt",
+        env!("CARGO_MANIFEST_DIR"),
+        solution.day()
+    );
+
+    std::fs::read_to_string(&path).map_err(|_| format!("no input at {}", path))
 }
 
-/// Define benchmarks for a single day with part1 and part2
-macro_rules! benches_day {
-    ($day_num:literal) => {
-        paste! {
-            use advent_of_code_2024::[<day $day_num>];
-
-            pub fn [<bench_day $day_num>](c: &mut Criterion) {
-                let mut group = c.benchmark_group(concat!("day", $day_num));
-                let input = get_day_input!($day_num);
-                group.bench_function(format!("day{}_part1", $day_num), |b| b.iter(|| [<day $day_num>]::part1(input)));
-                group.bench_function(format!("day{}_part2", $day_num), |b| b.iter(|| [<day $day_num>]::part2(input)));
+/// Define parse and solve benchmarks for every day in the registry whose input is available,
+/// skipping parts that are not implemented. The solve benchmarks reuse the parsed input.
+fn bench_days(c: &mut Criterion) {
+    let mut skipped = Vec::new();
+
+    for solution in DAYS {
+        let day = solution.day();
+        let input = match get_day_input(*solution) {
+            Ok(input) => input,
+            Err(reason) => {
+                skipped.push(format!("day{}: {}", day, reason));
+                continue;
+            }
+        };
+
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                skipped.push(format!("day{}: invalid input, {}", day, error));
+                continue;
             }
+        };
+
+        let mut group = c.benchmark_group(format!("day{}", day));
+        group.bench_function(format!("day{}_parse", day), |b| {
+            b.iter_with_large_drop(|| solution.parse(&input).unwrap())
+        });
+        group.bench_function(format!("day{}_part1_solve", day), |b| {
+            b.iter(|| solution.part1(&*parsed))
+        });
+
+        if solution.has_part2() {
+            group.bench_function(format!("day{}_part2_solve", day), |b| {
+                b.iter(|| solution.part2(&*parsed))
+            });
         }
-    };
-}
 
-/// Create benchmarks for included days
-macro_rules! benches {
-    ($($day_num:literal),*) => {
-        paste! {
-            $(
-                benches_day!($day_num);
-            )*
+        group.finish();
+    }
 
-            criterion_group!(benches, $([<bench_day $day_num>]),*);
-            criterion_main!(benches);
+    if !skipped.is_empty() {
+        eprintln!("Skipped {} of {} days:", skipped.len(), DAYS.len());
+        for reason in &skipped {
+            eprintln!("\t{}", reason);
         }
-    };
+    }
 }
 
-benches!(1, 2, 3, 4, 5, 7, 8, 9, 10, 11, 16, 19, 25);
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
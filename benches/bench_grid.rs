@@ -0,0 +1,61 @@
+use advent_of_code_2024::{day10, day16, day4, ParseError};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Get and parse the input for a single day, reporting it as skipped when it isn't available
+fn get_day_input<T>(day: u8, parse_input: fn(&str) -> Result<T, ParseError>) -> Option<T> {
+    let path = format!("{}/inputs/day{}.txt", env!("CARGO_MANIFEST_DIR"), day);
+
+    let Ok(input) = std::fs::read_to_string(&path) else {
+        eprintln!("Skipped day{}: no input at {}", day, path);
+        return None;
+    };
+
+    parse_input(&input)
+        .inspect_err(|error| eprintln!("Skipped day{}: invalid input, {}", day, error))
+        .ok()
+}
+
+/// Compare the plain grid layout against the sentinel padded one for the grid walking days
+fn bench_grid_layouts(c: &mut Criterion) {
+    if let Some(grid) = get_day_input(4, day4::parse_input) {
+        let padded = grid.padded();
+
+        let mut group = c.benchmark_group("day4_layout");
+        group.bench_function("grid_part1", |b| b.iter(|| day4::count_xmas(&grid)));
+        group.bench_function("padded_part1", |b| b.iter(|| day4::count_xmas(&padded)));
+        group.bench_function("grid_part2", |b| b.iter(|| day4::count_x_mas(&grid)));
+        group.bench_function("padded_part2", |b| b.iter(|| day4::count_x_mas(&padded)));
+        group.finish();
+    }
+
+    if let Some(map) = get_day_input(10, day10::parse_input) {
+        let padded = map.padded();
+
+        let mut group = c.benchmark_group("day10_layout");
+        group.bench_function("grid_part1", |b| b.iter(|| day10::trail_head_scores(&map)));
+        group.bench_function("padded_part1", |b| {
+            b.iter(|| day10::trail_head_scores(&padded))
+        });
+        group.bench_function("grid_part2", |b| b.iter(|| day10::trail_head_ratings(&map)));
+        group.bench_function("padded_part2", |b| {
+            b.iter(|| day10::trail_head_ratings(&padded))
+        });
+        group.finish();
+    }
+
+    if let Some(maze) = get_day_input(16, day16::parse_input) {
+        let padded = maze.padded();
+
+        let mut group = c.benchmark_group("day16_layout");
+        group.bench_function("grid_part1", |b| b.iter(|| day16::lowest_score(&maze)));
+        group.bench_function("padded_part1", |b| b.iter(|| day16::lowest_score(&padded)));
+        group.bench_function("grid_part2", |b| b.iter(|| day16::best_path_tiles(&maze)));
+        group.bench_function("padded_part2", |b| {
+            b.iter(|| day16::best_path_tiles(&padded))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_grid_layouts);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use advent_of_code_2024::generate::Size;
+use advent_of_code_2024::DAYS;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Seed of every generated input, so that runs compare the same inputs
+const SEED: u64 = 2024;
+
+/// Side of the generated grids, the line based days get its square as their number of lines
+const SIDES: [usize; 3] = [20, 40, 80];
+
+/// Parse and solve generated inputs of growing size for every day with a generator, to show how
+/// each part scales with its input
+fn bench_scaling(c: &mut Criterion) {
+    for solution in DAYS {
+        let day = solution.day();
+
+        let mut group = c.benchmark_group(format!("day{}_scaling", day));
+        group.sample_size(10);
+
+        for side in SIDES {
+            let Some(input) = solution.generate(SEED, &Size::square(side)) else {
+                eprintln!("Skipped day{}: no generator", day);
+                break;
+            };
+
+            let parsed = solution.parse(&input).unwrap();
+
+            group.throughput(Throughput::Bytes(input.len() as u64));
+            group.bench_with_input(BenchmarkId::new("parse", side), &input, |b, input| {
+                b.iter_with_large_drop(|| solution.parse(input).unwrap())
+            });
+            group.bench_function(BenchmarkId::new("part1", side), |b| {
+                b.iter(|| solution.part1(&*parsed))
+            });
+
+            if solution.has_part2() {
+                group.bench_function(BenchmarkId::new("part2", side), |b| {
+                    b.iter(|| solution.part2(&*parsed))
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_scaling);
+criterion_main!(benches);
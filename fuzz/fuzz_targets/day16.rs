@@ -0,0 +1,6 @@
+#![no_main]
+
+use advent_of_code_2024::day16::Day16;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| advent_of_code_2024_fuzz::run::<Day16>(input));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use advent_of_code_2024::day2::Day2;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| advent_of_code_2024_fuzz::run::<Day2>(input));
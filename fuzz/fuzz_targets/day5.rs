@@ -0,0 +1,6 @@
+#![no_main]
+
+use advent_of_code_2024::day5::Day5;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| advent_of_code_2024_fuzz::run::<Day5>(input));
@@ -0,0 +1,15 @@
+//! Fuzz targets for every day, one binary per day in `fuzz_targets/`. Run one with
+//! `cargo +nightly fuzz run day9` from the crate root, seeding its corpus from the examples with
+//! `mkdir -p fuzz/corpus/day9 && cp examples/2024/day9*.txt fuzz/corpus/day9/` beforehand.
+
+use advent_of_code_2024::Solution;
+
+/// Parses arbitrary text and, when the parser accepts it, solves both parts. Invalid input must be
+/// rejected with a parse error, and a part may fail with a solve error; any panic, overflow or out
+/// of bounds index is a bug.
+pub fn run<S: Solution>(input: &str) {
+    if let Ok(parsed) = S::parse(input) {
+        let _ = S::part1(&parsed);
+        let _ = S::part2(&parsed);
+    }
+}
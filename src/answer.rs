@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter};
+
+/// The answer to a part, whatever type the day computes it in.
+///
+/// Integers convert into the variant matching their sign, so the same number is equal regardless
+/// of the type it was computed in, e.g. `Answer::from(7u16) == Answer::from(7i64)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u128),
+    /// Only used for negative numbers.
+    Signed(i128),
+    /// Anything that isn't a number, e.g. a comma separated list.
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(number) => write!(f, "{}", number),
+            Answer::Signed(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($type:ty),*) => {
+        $(impl From<$type> for Answer {
+            fn from(number: $type) -> Self {
+                Answer::Unsigned(number as u128)
+            }
+        })*
+    };
+}
+
+macro_rules! from_signed {
+    ($($type:ty),*) => {
+        $(impl From<$type> for Answer {
+            fn from(number: $type) -> Self {
+                match u128::try_from(number) {
+                    Ok(number) => Answer::Unsigned(number),
+                    Err(_) => Answer::Signed(number as i128),
+                }
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// Compares against an answer as it is written down, e.g. in `inputs/answers.txt`.
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        // Numbers are written without leading zeros or a plus sign
+        match self {
+            Answer::Unsigned(number) => {
+                other.parse() == Ok(*number) && (other == "0" || !other.starts_with(['0', '+']))
+            }
+            Answer::Signed(number) => other.parse() == Ok(*number) && !other.starts_with("-0"),
+            Answer::Text(text) => text == other,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(7u16), Answer::from(7i64));
+        assert_eq!(Answer::Signed(-3), Answer::from(-3i8));
+        assert_eq!(Answer::from(u128::MAX), u128::MAX.to_string().as_str());
+        assert_eq!(Answer::from("4,6,3,5"), "4,6,3,5");
+        assert_ne!(Answer::from(11usize), "011");
+        assert_ne!(Answer::from(11usize), "+11");
+        assert_eq!(Answer::from(0u8), "0");
+        assert_eq!("-12", Answer::from(-12isize).to_string());
+    }
+}
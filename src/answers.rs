@@ -0,0 +1,373 @@
+use crate::{Answer, DynSolution, ParseError, Part, SolveError};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::path::Path;
+
+/// Known correct answers for the real puzzle inputs, read from `inputs/answers.txt` next to the
+/// inputs themselves. Every line holds a single answer:
+///
+/// ```text
+/// day1 part1: 2196996
+/// day1 part2: 23655822
+/// ```
+///
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    inner: HashMap<(u8, Part), String>,
+}
+
+/// A line of the answers file or the example manifest that doesn't follow their
+/// `name partN: answer` format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswersError {
+    /// One based line number.
+    pub line: usize,
+    pub text: String,
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: expected \"<name> partN: answer\", found {:?}",
+            self.line, self.text
+        )
+    }
+}
+
+impl Error for AnswersError {}
+
+/// Splits a key like `day1 part2` into what comes before the part, and the part.
+fn split_key(key: &str) -> Option<(&str, Part)> {
+    let (name, part) = key.trim().rsplit_once(' ')?;
+
+    let part = match part {
+        "part1" => Part::One,
+        "part2" => Part::Two,
+        _ => return None,
+    };
+
+    Some((name.trim(), part))
+}
+
+/// Parses the `key partN: answer` lines shared by the answers file and the example manifest.
+fn parse_entries<K: Eq + Hash>(
+    input: &str,
+    parse_name: impl Fn(&str) -> Option<K>,
+) -> Result<HashMap<(K, Part), String>, AnswersError> {
+    let parse_key = |key| {
+        let (name, part) = split_key(key)?;
+        Some((parse_name(name)?, part))
+    };
+
+    let mut inner = HashMap::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let entry = line
+            .split_once(':')
+            .map(|(key, answer)| (key, answer.trim()))
+            .filter(|(_, answer)| !answer.is_empty())
+            .and_then(|(key, answer)| Some((parse_key(key)?, answer.to_string())));
+
+        let Some((key, answer)) = entry else {
+            return Err(AnswersError {
+                line: index + 1,
+                text: line.to_string(),
+            });
+        };
+
+        inner.insert(key, answer);
+    }
+
+    Ok(inner)
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, AnswersError> {
+        let inner = parse_entries(input, |name| name.strip_prefix("day")?.parse().ok())?;
+
+        Ok(Self { inner })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.inner.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Expected answers for the examples of every day, read from `examples/2024/manifest.txt`. Every
+/// line holds the answer to one part of an example file:
+///
+/// ```text
+/// day16_1a.txt part1: 7036
+/// day8_part_2.txt part2: 9
+/// ```
+///
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    inner: HashMap<(String, Part), String>,
+}
+
+impl Manifest {
+    pub fn parse(input: &str) -> Result<Self, AnswersError> {
+        let inner = parse_entries(input, |name| Some(name.to_string()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Expected answer for the example, given by its file name.
+    pub fn get(&self, file: &str, part: Part) -> Option<&str> {
+        self.inner
+            .get(&(file.to_string(), part))
+            .map(String::as_str)
+    }
+
+    /// File names of every example with at least one answer.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.inner.keys().map(|(file, _)| file.as_str())
+    }
+}
+
+/// Result of checking a single part against its recorded answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect {
+        expected: String,
+        actual: Answer,
+    },
+    InvalidInput(ParseError),
+    /// The input parsed, but the part has no answer for it.
+    Unsolved(SolveError),
+    /// There is no input file for the day.
+    MissingInput,
+    /// The part was solved, but there is nothing to compare it to.
+    MissingAnswer(Answer),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect { .. } | Verdict::InvalidInput(_) | Verdict::Unsolved(_)
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "ok"),
+            Verdict::Incorrect { expected, actual } => {
+                write!(f, "FAILED, expected {} but got {}", expected, actual)
+            }
+            Verdict::InvalidInput(error) => write!(f, "FAILED, {}", error),
+            Verdict::Unsolved(error) => write!(f, "FAILED, {}", error),
+            Verdict::MissingInput => write!(f, "skipped, no input"),
+            Verdict::MissingAnswer(actual) => write!(f, "skipped, no answer for {}", actual),
+        }
+    }
+}
+
+/// Solves both parts of a day on `inputs/dayN.txt` below `root` and compares them to the
+/// answers. Parts the day doesn't implement are left out.
+pub fn verify(solution: &dyn DynSolution, root: &Path, answers: &Answers) -> Vec<(Part, Verdict)> {
+    let path = root
+        .join("inputs")
+        .join(format!("day{}.txt", solution.day()));
+    let Ok(input) = std::fs::read_to_string(path) else {
+        return vec![(Part::One, Verdict::MissingInput)];
+    };
+
+    judge(solution, &input, |part| answers.get(solution.day(), part))
+}
+
+/// Solves every example of a day listed below `root` and compares the parts that have an answer
+/// in the manifest. Examples without any answer report every part as [`Verdict::MissingAnswer`].
+pub fn verify_examples(
+    solution: &dyn DynSolution,
+    root: &Path,
+    manifest: &Manifest,
+) -> Vec<(String, Part, Verdict)> {
+    let mut verdicts = Vec::new();
+
+    for example in solution.examples() {
+        let path = root.join(example);
+        let file = path
+            .file_name()
+            .map_or_else(String::new, |file| file.to_string_lossy().into_owned());
+
+        let Ok(input) = std::fs::read_to_string(&path) else {
+            verdicts.push((file, Part::One, Verdict::MissingInput));
+            continue;
+        };
+
+        let judged = judge(solution, &input, |part| manifest.get(&file, part));
+
+        // Many examples only come with the answer to one of the parts
+        let listed = judged
+            .iter()
+            .any(|(_, verdict)| !matches!(verdict, Verdict::MissingAnswer(_)));
+
+        verdicts.extend(
+            judged
+                .into_iter()
+                .filter(|(_, verdict)| !listed || !matches!(verdict, Verdict::MissingAnswer(_)))
+                .map(|(part, verdict)| (file.clone(), part, verdict)),
+        );
+    }
+
+    verdicts
+}
+
+/// Solves both parts of the input and compares them to the expected answers.
+fn judge<'a>(
+    solution: &dyn DynSolution,
+    input: &str,
+    expected: impl Fn(Part) -> Option<&'a str>,
+) -> Vec<(Part, Verdict)> {
+    let parsed = match solution.parse(input) {
+        Ok(parsed) => parsed,
+        Err(error) => return vec![(Part::One, Verdict::InvalidInput(error))],
+    };
+
+    let actual = [
+        (Part::One, Some(solution.part1(&*parsed))),
+        (Part::Two, solution.part2(&*parsed)),
+    ];
+
+    actual
+        .into_iter()
+        .filter_map(|(part, actual)| {
+            let actual = match actual? {
+                Ok(actual) => actual,
+                Err(error) => return Some((part, Verdict::Unsolved(error))),
+            };
+
+            let verdict = match expected(part) {
+                None => Verdict::MissingAnswer(actual),
+                Some(expected) if actual == expected => Verdict::Correct,
+                Some(expected) => Verdict::Incorrect {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+
+            Some((part, verdict))
+        })
+        .collect()
+}
+
+/// Reads `inputs/answers.txt` below `root`, an absent file has no answers.
+pub fn read_answers(root: &Path) -> Result<Answers, AnswersError> {
+    match std::fs::read_to_string(root.join("inputs").join("answers.txt")) {
+        Ok(input) => Answers::parse(&input),
+        Err(_) => Ok(Answers::default()),
+    }
+}
+
+/// Reads `examples/2024/manifest.txt` below `root`, an absent file has no answers.
+pub fn read_manifest(root: &Path) -> Result<Manifest, AnswersError> {
+    let path = root.join("examples").join("2024").join("manifest.txt");
+
+    match std::fs::read_to_string(path) {
+        Ok(input) => Manifest::parse(&input),
+        Err(_) => Ok(Manifest::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DAYS;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("# comment\nday1 part1: 11\n\nday25 part2: Merry\n").unwrap();
+
+        assert_eq!(Some("11"), answers.get(1, Part::One));
+        assert_eq!(Some("Merry"), answers.get(25, Part::Two));
+        assert_eq!(None, answers.get(1, Part::Two));
+
+        let error = Answers::parse("day1 part1: 11\nday2 part3: 4\n").unwrap_err();
+        assert_eq!(2, error.line);
+
+        let manifest = Manifest::parse(
+            "day16_1a.txt part1: 7036
+day8_part_2.txt  part2: 9
+",
+        )
+        .unwrap();
+        assert_eq!(Some("9"), manifest.get("day8_part_2.txt", Part::Two));
+        assert_eq!(None, manifest.get("day8_part_2.txt", Part::One));
+    }
+
+    /// Checks every example against the answers in the manifest, and that the manifest only lists
+    /// examples some day knows about. An example without an answer fails too, as does a missing
+    /// manifest, which would otherwise leave every example unanswered.
+    #[test]
+    fn test_examples() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let path = root.join("examples").join("2024").join("manifest.txt");
+        assert!(path.is_file(), "{} is missing", path.display());
+        let manifest = read_manifest(root).unwrap();
+
+        let mut failures = Vec::new();
+
+        for solution in DAYS {
+            for (file, part, verdict) in verify_examples(*solution, root, &manifest) {
+                let line = format!("{} - {}: {}", file, part, verdict);
+
+                if verdict != Verdict::Correct {
+                    failures.push(line);
+                }
+            }
+        }
+
+        for file in manifest.files() {
+            let known = DAYS
+                .iter()
+                .flat_map(|solution| solution.examples())
+                .any(|example| Path::new(example).file_name() == Some(file.as_ref()));
+
+            if !known {
+                failures.push(format!("{}: not an example of any day", file));
+            }
+        }
+
+        failures.sort();
+        failures.dedup();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    /// Checks every real input that is present against its recorded answer.
+    #[test]
+    fn test_real_inputs() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let answers = read_answers(root).unwrap();
+
+        let mut failures = Vec::new();
+
+        for solution in DAYS {
+            for (part, verdict) in verify(*solution, root, &answers) {
+                let line = format!("Day {} - {}: {}", solution.day(), part, verdict);
+
+                if verdict.is_failure() {
+                    failures.push(line);
+                } else if verdict != Verdict::Correct {
+                    println!("{}", line);
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -1,58 +1,99 @@
+use crate::generate::{self, Rng, Size};
+use crate::parse::lines;
+use crate::{ParseError, Solution, SolveError};
 use itertools::*;
 use rustc_hash::{FxBuildHasher, FxHashMap};
 
-type Output = u32;
+type Output = u64;
 type Input = (Vec<u32>, Vec<u32>);
 
-pub fn input_generator(input: &str) -> Input {
-    input
-        .lines()
-        .map(|line| line.split_once("   ").unwrap())
-        .map(|(first, second)| {
-            (
-                first.parse::<u32>().unwrap(),
-                second.parse::<u32>().unwrap(),
-            )
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    lines(1, input)
+        .map(|line| {
+            let (first, second) = line.split_once("   ")?;
+            Ok((line.number::<u32>(first)?, line.number::<u32>(second)?))
         })
-        .unzip()
+        .process_results(|pairs| pairs.unzip())
 }
 
-pub fn part1(input: &str) -> Output {
-    let (left, right) = input_generator(input);
-
-    left.into_iter()
+pub fn part1((left, right): &Input) -> Output {
+    left.iter()
         .sorted()
         .zip_eq(right.iter().sorted())
-        .map(|(left, right)| left.abs_diff(*right))
+        .map(|(left, right)| left.abs_diff(*right) as Output)
         .sum::<Output>()
 }
 
-pub fn part2(input: &str) -> Output {
-    let (left, right) = input_generator(input);
-
+pub fn part2((left, right): &Input) -> Output {
     let mut counts = FxHashMap::with_capacity_and_hasher(right.len(), FxBuildHasher);
     right
         .iter()
         .for_each(|item| *counts.entry(item).or_default() += 1);
 
     left.iter()
-        .map(|value| *counts.get(value).unwrap_or(&0) as u32 * value)
+        .map(|value| *counts.get(value).unwrap_or(&0) as Output * *value as Output)
         .sum::<Output>()
 }
 
+/// `count` lines of location ids, where the right list reuses some of the ids on the left.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let left = (0..size.count)
+        .map(|_| rng.u32(10000..100000))
+        .collect::<Vec<_>>();
+
+    generate::lines(&left, |id| {
+        let right = match rng.bool() {
+            true => left[rng.usize(..left.len())],
+            false => rng.u32(10000..100000),
+        };
+
+        format!("{}   {}", id, right)
+    })
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day1.txt"];
+
+    type Input = Input;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = include_str!("../../examples/2024/day1.txt");
-
     #[test]
-    fn test_part1() {
-        assert_eq!(11, part1(TEST_INPUT));
-    }
+    fn test_parse_error() {
+        let error = input_generator("3   4\n4  3\n").unwrap_err();
+        assert_eq!(
+            (2, 2, Some(' ')),
+            (error.line, error.column, error.character)
+        );
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(31, part2(TEST_INPUT));
+        let error = input_generator("3   4\n4   x\n").unwrap_err();
+        assert_eq!(
+            (2, 5, Some('x')),
+            (error.line, error.column, error.character)
+        );
     }
 }
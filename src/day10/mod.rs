@@ -1,174 +1,193 @@
-use arrayvec::ArrayVec;
-use fixedbitset::FixedBitSet;
-use std::collections::VecDeque;
+use crate::direction::Direction;
+use crate::generate::{self, Rng, Size};
+use crate::grid::{Grid, Layout, PaddedGrid, Sentinel};
+use crate::render::{Color, Overlay, Picture, Pixel};
+use crate::search::{Bfs, PathCounter};
+use crate::{ParseError, Solution, SolveError};
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-
-#[derive(EnumIter, Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+
+/// Height of a single position on the topographic map, from 0 to 9.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Height(u8);
+
+impl TryFrom<char> for Height {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        value
+            .to_digit(10)
+            .map(|digit| Height(digit as u8))
+            .ok_or(())
+    }
 }
 
-#[derive(Debug)]
-struct Grid {
-    inner: [u8; 1600],
-    trail_heads: ArrayVec<u16, 320>,
-    width: usize,
-    height: usize,
+impl Sentinel for Height {
+    const SENTINEL: Self = Height(u8::MAX);
 }
 
-struct GridFinder {
-    queue: VecDeque<(usize, u8)>,
+#[derive(Debug)]
+pub struct TopographicMap<G = Grid<Height>> {
+    grid: G,
+    trail_heads: Vec<usize>,
 }
 
-impl GridFinder {
-    pub fn new(grid: &Grid) -> Self {
-        Self {
-            queue: VecDeque::with_capacity(grid.width * grid.height),
-        }
-    }
+impl TopographicMap {
+    pub fn padded(&self) -> TopographicMap<PaddedGrid<Height>> {
+        let grid = self.grid.padded();
+        let trail_heads = self
+            .trail_heads
+            .iter()
+            .map(|trail_head| grid.padded_index(*trail_head))
+            .collect();
 
-    fn find_trails_starting_at(
-        &mut self,
-        grid: &Grid,
-        trail_head: u16,
-        mut trail_end: impl FnMut(usize),
-    ) {
-        self.queue.clear();
-        self.queue.push_front((trail_head as usize, 0));
-
-        while let Some((index, height)) = self.queue.pop_front() {
-            if height == 9 {
-                trail_end(index);
-                continue;
-            }
-
-            for direction in Direction::iter() {
-                let Some(next_index) = grid.move_towards(index, direction) else {
-                    continue;
-                };
-
-                let next_height = grid.at(next_index);
-
-                if next_height != height + 1 {
-                    continue;
-                }
-
-                self.queue.push_front((next_index, next_height));
-            }
-        }
+        TopographicMap { grid, trail_heads }
     }
 }
 
-impl Grid {
-    fn from_input(input: &str) -> Self {
-        let width = input.lines().next().unwrap().len();
-        let mut height = 0;
-
-        let mut inner = [u8::MAX; 1600];
-        let mut trail_heads = ArrayVec::new();
-
-        input
-            .lines()
-            .enumerate()
-            .filter(|(_, line)| !line.is_empty())
-            .for_each(|(y, line)| {
-                height += 1;
-                line.chars().enumerate().for_each(|(x, c)| {
-                    let value = c.to_digit(10).unwrap() as u8;
-                    let index = y * width + x;
-
-                    inner[index] = value;
-
-                    if value == 0 {
-                        trail_heads.push(index as u16);
-                    }
-                })
-            });
-
-        Self {
-            inner,
-            width,
-            height,
-            trail_heads,
-        }
-    }
+/// Neighbouring positions that are exactly one higher than the given one.
+fn uphill<G: Layout<Height>>(grid: &G, index: usize) -> impl Iterator<Item = usize> + '_ {
+    let Height(height) = grid[index];
 
-    fn at(&self, index: usize) -> u8 {
-        self.inner[index]
-    }
+    Direction::iter()
+        .filter_map(move |direction| grid.move_towards(index, direction))
+        .filter(move |next| grid[*next] == Height(height + 1))
+}
 
-    fn move_towards(&self, index: usize, direction: Direction) -> Option<usize> {
-        match direction {
-            Direction::Up => {
-                if index < self.width {
-                    return None;
-                }
-
-                Some(index - self.width)
-            }
-            Direction::Down => {
-                if index >= (self.height - 1) * self.width {
-                    return None;
-                }
-
-                Some(index + self.width)
-            }
-            Direction::Left => {
-                if index.rem_euclid(self.width) == 0 {
-                    return None;
-                }
-
-                Some(index - 1)
-            }
-            Direction::Right => {
-                if index.rem_euclid(self.width) == self.width - 1 {
-                    return None;
-                }
-
-                Some(index + 1)
-            }
-        }
-    }
+pub fn parse_input(input: &str) -> Result<TopographicMap, ParseError> {
+    let grid = Grid::parse(10, input)?;
+    let trail_heads = grid.positions(|height| *height == Height(0)).collect();
+
+    Ok(TopographicMap { grid, trail_heads })
+}
+
+pub fn part1(map: &TopographicMap) -> usize {
+    trail_head_scores(map)
+}
+
+pub fn part2(map: &TopographicMap) -> usize {
+    trail_head_ratings(map)
 }
 
-pub fn part1(input: &str) -> usize {
-    let grid = Grid::from_input(input);
-    let mut finder = GridFinder::new(&grid);
-    let mut trail_ends = FixedBitSet::with_capacity(grid.width * grid.height);
+/// Part 1 for any grid layout.
+pub fn trail_head_scores<G: Layout<Height>>(map: &TopographicMap<G>) -> usize {
+    let mut bfs = Bfs::new(map.grid.size());
 
-    grid.trail_heads
+    map.trail_heads
         .iter()
         .map(|trail_head| {
-            trail_ends.clear();
-            finder.find_trails_starting_at(&grid, *trail_head, |trail_end| {
-                trail_ends.insert(trail_end);
-            });
-
-            trail_ends.count_ones(..)
+            bfs.run([*trail_head], |index| uphill(&map.grid, index));
+            bfs.reached()
+                .iter()
+                .filter(|index| map.grid[**index] == Height(9))
+                .count()
         })
         .sum()
 }
 
-pub fn part2(input: &str) -> usize {
-    let grid = Grid::from_input(input);
-    let mut finder = GridFinder::new(&grid);
+/// Part 2 for any grid layout.
+pub fn trail_head_ratings<G: Layout<Height>>(map: &TopographicMap<G>) -> usize {
+    let mut counter = PathCounter::new(map.grid.size());
 
-    grid.trail_heads
+    map.trail_heads
         .iter()
         .map(|trail_head| {
-            let mut rating = 0;
+            counter.count(
+                *trail_head,
+                |index| uphill(&map.grid, index),
+                |index| map.grid[index] == Height(9),
+            )
+        })
+        .sum()
+}
 
-            finder.find_trails_starting_at(&grid, *trail_head, |_| {
-                rating += 1;
-            });
+/// The map shaded by height, with the trail heads and every nine reachable from one of them
+/// highlighted.
+pub fn render(map: &TopographicMap) -> Picture {
+    let mut picture = Picture::from_grid(&map.grid, |Height(height)| {
+        let symbol = char::from(b'0' + height);
+        Pixel::new(symbol, Color::grey(0.2 + *height as f32 / 12.0))
+    });
+
+    let mut bfs = Bfs::new(map.grid.size());
+    bfs.run(map.trail_heads.iter().copied(), |index| {
+        uphill(&map.grid, index)
+    });
+    let peaks = bfs
+        .reached()
+        .iter()
+        .copied()
+        .filter(|index| map.grid[*index] == Height(9));
+
+    let trail_head = Overlay {
+        background: Color::GREEN,
+        symbol: None,
+    };
+    let peak = Overlay {
+        background: Color::GOLD,
+        symbol: None,
+    };
+
+    picture.highlight(map.trail_heads.iter().copied(), trail_head);
+    picture.highlight(peaks, peak);
+
+    picture
+}
 
-            rating
+/// A `width` by `height` map of slopes rising from 0 to 9 and back, shifted a little from row to
+/// row and with some random heights mixed in, so that there are plenty of hiking trails.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let ramp = |step: usize| match step % 18 {
+        step @ 0..=9 => step as u8,
+        step => 18 - step as u8,
+    };
+
+    let mut shift = 0;
+    let shifts = (0..size.height)
+        .map(|_| {
+            shift += rng.usize(..=2);
+            shift
         })
-        .sum()
+        .collect::<Vec<_>>();
+
+    generate::grid(size.width, size.height, |x, y| {
+        let height = match rng.u8(..6) {
+            0 => rng.u8(..10),
+            _ => ramp(x + shifts[y]),
+        };
+
+        char::from(b'0' + height)
+    })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day10.txt"];
+
+    type Input = TopographicMap;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    fn render(input: &Self::Input) -> Option<Picture> {
+        Some(render(input))
+    }
 }
 
 #[cfg(test)]
@@ -177,13 +196,28 @@ mod tests {
 
     const TEST_INPUT: &str = include_str!("../../examples/2024/day10.txt");
 
+    /// The examples on the padded layout, the manifest covers the plain one.
     #[test]
-    fn test_part1() {
-        assert_eq!(36, part1(TEST_INPUT));
+    fn test_padded() {
+        assert_eq!(
+            36,
+            trail_head_scores(&parse_input(TEST_INPUT).unwrap().padded())
+        );
+        assert_eq!(
+            81,
+            trail_head_ratings(&parse_input(TEST_INPUT).unwrap().padded())
+        );
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(81, part2(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("0123\n1.34\n").unwrap_err();
+        assert_eq!(
+            (2, 2, Some('.')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("0123\n123\n").unwrap_err();
+        assert_eq!((2, 4, None), (error.line, error.column, error.character));
     }
 }
@@ -1,35 +1,50 @@
+use crate::generate::{Rng, Size};
+use crate::parse::{lines, ErrorKind};
+use crate::stats::Counter;
+use crate::{ParseError, Solution, SolveError};
+use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
-type Stone = usize;
+type Stone = u64;
 type Iteration = usize;
 
+static CACHE_HITS: Counter = Counter::new("cache hits");
+static CACHE_MISSES: Counter = Counter::new("cache misses");
+
+const OVERFLOW: SolveError =
+    SolveError::new(11, "a stone's number or the number of stones overflows");
+
+/// Number of stones the stone turns into, or `None` when a number or the count overflows.
 fn blink(
     stone: Stone,
     iterations_left: Iteration,
-    cache: &mut FxHashMap<(Stone, Iteration), usize>,
-) -> usize {
+    cache: &mut FxHashMap<(Stone, Iteration), u64>,
+) -> Option<u64> {
     // Base case: If there are no more iterations there is just one stone
     if iterations_left == 0 {
-        return 1;
+        return Some(1);
     }
 
     // Base case: If there is a cache hit, use that value
     if let Some(output) = cache.get(&(stone, iterations_left)) {
-        return *output;
+        CACHE_HITS.increment();
+        return Some(*output);
     }
 
+    CACHE_MISSES.increment();
+
     // Rule 1: If the stone is engraved with the number 0, it is replaced by a stone engraved with the number 1.
     if stone == 0 {
         // Optimisation: This iteration zero is converted into one and in the next iteration it is
         //  converted into 2024. So let's skip ahead a little bit.
         if iterations_left <= 2 {
-            return 1;
+            return Some(1);
         }
 
-        let value = blink(2024, iterations_left - 2, cache);
+        let value = blink(2024, iterations_left - 2, cache)?;
         cache.insert((0, iterations_left), value);
         cache.insert((1, iterations_left - 1), value);
-        return value;
+        return Some(value);
     }
 
     // Rule 2: If the stone is engraved with a number that has an even number of digits, it is replaced
@@ -39,58 +54,167 @@ fn blink(
     if number_of_digits % 2 == 0 {
         // Optimisation: Don't perform the actual manipulation of numbers if it's not required
         if iterations_left == 1 {
-            return 2;
+            return Some(2);
         }
 
         let number_of_digits = number_of_digits / 2;
 
         let value = blink(
-            stone % 10_usize.pow(number_of_digits),
+            stone % 10_u64.pow(number_of_digits),
             iterations_left - 1,
             cache,
-        ) + blink(
-            stone / 10_usize.pow(number_of_digits),
+        )?
+        .checked_add(blink(
+            stone / 10_u64.pow(number_of_digits),
             iterations_left - 1,
             cache,
-        );
+        )?)?;
 
         cache.insert((stone, iterations_left), value);
-        return value;
+        return Some(value);
     }
 
     // Rule 3: The stone is replaced by a new stone; the old stone's number multiplied by 2024 is
     //          engraved on the new stone.
-    let value = blink(stone * 2024, iterations_left - 1, cache);
+    let value = blink(stone.checked_mul(2024)?, iterations_left - 1, cache)?;
     cache.insert((stone, iterations_left), value);
-    value
+    Some(value)
 }
 
-pub fn part1(input: &str) -> usize {
-    inner(input, 25)
+/// The stones, all on the first and only line.
+pub fn parse_input(input: &str) -> Result<Vec<Stone>, ParseError> {
+    let mut lines = lines(11, input);
+    let Some(line) = lines.next() else {
+        return Err(ParseError::missing(11, input, "the stones"));
+    };
+
+    if let Some(line) = lines.next() {
+        return Err(line.error(0, ErrorKind::Expected("the end of the input")));
+    }
+
+    line.text
+        .split_whitespace()
+        .map(|stone| line.number::<Stone>(stone))
+        .collect()
 }
 
-pub fn part2(input: &str) -> usize {
-    inner(input, 75)
+pub fn part1(input: &[Stone]) -> Result<u64, SolveError> {
+    inner(input, 25).ok_or(OVERFLOW)
 }
 
-fn inner(input: &str, iterations: usize) -> usize {
-    let mut cache: FxHashMap<(Stone, Iteration), usize> = FxHashMap::default();
+pub fn part2(input: &[Stone]) -> Result<u64, SolveError> {
+    inner(input, 75).ok_or(OVERFLOW)
+}
 
-    input
-        .lines()
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .map(|stone| stone.parse::<Stone>().unwrap())
-        .map(|stone| blink(stone, iterations, &mut cache))
-        .sum()
+/// Number of stones after every blink, starting with the first, until a number or the count
+/// overflows. The memo is kept between blinks, so later blinks reuse the counts of earlier ones.
+pub fn blinks(input: &[Stone]) -> Blinks<'_> {
+    Blinks {
+        stones: input,
+        iterations: 0,
+        cache: FxHashMap::default(),
+    }
+}
+
+pub struct Blinks<'a> {
+    stones: &'a [Stone],
+    iterations: Iteration,
+    cache: FxHashMap<(Stone, Iteration), u64>,
+}
+
+impl Iterator for Blinks<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterations += 1;
+
+        count(self.stones, self.iterations, &mut self.cache)
+    }
+}
+
+fn count(
+    stones: &[Stone],
+    iterations: Iteration,
+    cache: &mut FxHashMap<(Stone, Iteration), u64>,
+) -> Option<u64> {
+    stones.iter().try_fold(0_u64, |sum, stone| {
+        sum.checked_add(blink(*stone, iterations, cache)?)
+    })
+}
+
+fn inner(input: &[Stone], iterations: usize) -> Option<u64> {
+    let mut cache: FxHashMap<(Stone, Iteration), u64> = FxHashMap::default();
+    count(input, iterations, &mut cache)
+}
+
+/// A line of `count` stones engraved with numbers of up to seven digits.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let stones = (0..size.count).map(|_| rng.u64(..10_000_000)).join(" ");
+    format!("{}\n", stones)
 }
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day11.txt"];
+
+    const COUNTERS: &'static [&'static Counter] = &[&CACHE_HITS, &CACHE_MISSES];
+
+    type Input = Vec<Stone>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        assert_eq!(55312, part1("125 17"));
+    fn test_blinks() {
+        let stones = parse_input("125 17").unwrap();
+        let counts = blinks(&stones).take(25).collect::<Vec<_>>();
+
+        assert_eq!(&[3, 4, 5, 9, 13, 22], &counts[..6]);
+        assert_eq!(part1(&stones).ok().as_ref(), counts.last());
+        assert_eq!(part2(&stones).ok(), blinks(&stones).nth(74));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let stones = parse_input("1000000000000000000\n").unwrap();
+        assert_eq!(Err(OVERFLOW), part1(&stones));
+        assert_eq!(Err(OVERFLOW), part2(&stones));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("125 17a").unwrap_err();
+        assert_eq!(
+            (1, 7, Some('a')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("125 17\n0\n").unwrap_err();
+        assert_eq!(
+            (2, 1, Some('0')),
+            (error.line, error.column, error.character)
+        );
     }
 }
@@ -1,303 +1,340 @@
+use crate::direction::Direction;
+use crate::generate::{self, Rng, Size};
+use crate::grid::{Grid, Layout, PaddedGrid, Sentinel};
+use crate::render::{Color, Overlay, Picture, Pixel};
+use crate::search::{Dijkstra, ShortestPaths, State, HEAP_POPS, HEAP_PUSHES, STALE_ENTRIES};
+use crate::stats::Counter;
+use crate::{ParseError, Solution, SolveError};
 use arrayvec::ArrayVec;
 use fixedbitset::FixedBitSet;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
+use strum::EnumCount;
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
-
-#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd)]
-enum Direction {
-    Up,
-    Left,
-    Down,
-    Right,
-}
 
-impl Direction {
-    fn neighbours(&self) -> (Self, Self) {
-        match self {
-            Direction::Down | Direction::Up => (Direction::Left, Direction::Right),
-            Direction::Left | Direction::Right => (Direction::Up, Direction::Down),
-        }
-    }
-
-    fn index(&self) -> usize {
-        match self {
-            Direction::Up => 0,
-            Direction::Left => 1,
-            Direction::Down => 2,
-            Direction::Right => 3,
-        }
-    }
+/// A tile of the maze and the direction the reindeer is facing on it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub index: usize,
+    pub direction: Direction,
 }
 
-fn move_towards(index: usize, direction: Direction, grid: &Grid) -> Option<usize> {
-    match direction {
-        Direction::Up => {
-            if index < grid.width {
-                return None;
-            }
-
-            Some(index - grid.width)
-        }
-        Direction::Down => {
-            if index >= (grid.height - 1) * grid.width {
-                return None;
-            }
-
-            Some(index + grid.width)
-        }
-        Direction::Left => {
-            if index.rem_euclid(grid.width) == 0 {
-                return None;
-            }
+/// Positions reachable in a single step, with the score each costs.
+type Moves = ArrayVec<(Position, usize), 3>;
 
-            Some(index - 1)
-        }
-        Direction::Right => {
-            if index.rem_euclid(grid.width) == grid.width - 1 {
-                return None;
-            }
-
-            Some(index + 1)
-        }
+impl State for Position {
+    fn index(&self) -> usize {
+        self.index * Direction::COUNT + self.direction.index()
     }
 }
 
-#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug)]
-struct Position {
-    index: usize,
-    direction: Direction,
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Tile {
+    Wall,
+    Open,
+    Start,
+    End,
 }
 
-#[derive(Eq, PartialEq)]
-struct HeapState {
-    position: Position,
-    previous_position: Option<Position>,
-    value: usize,
-}
+impl TryFrom<char> for Tile {
+    type Error = ();
 
-impl Ord for HeapState {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Notice that we flip the ordering on costs.
-        // In case of a tie we compare positions - this step is necessary
-        // to make implementations of `PartialEq` and `Ord` consistent.
-        other
-            .value
-            .cmp(&self.value)
-            .then_with(|| self.position.cmp(&other.position))
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Open),
+            'S' => Ok(Tile::Start),
+            'E' => Ok(Tile::End),
+            _ => Err(()),
+        }
     }
 }
 
-impl PartialOrd for HeapState {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+impl Sentinel for Tile {
+    const SENTINEL: Self = Tile::Wall;
 }
 
-struct Grid {
-    inner: FixedBitSet,
-    width: usize,
-    height: usize,
+#[derive(Debug)]
+pub struct Maze<G = Grid<Tile>> {
+    grid: G,
     source: usize,
     target: usize,
 }
 
-impl Grid {
-    fn size(&self) -> usize {
-        self.width * self.height
-    }
-
-    fn from_input(input: &str) -> Self {
-        let width = input.lines().next().unwrap().len();
-        let height = input.lines().count();
-
-        let mut source = 0;
-        let mut target = 0;
-
-        let mut inner = FixedBitSet::with_capacity(width * height);
-
-        for (y, line) in input.lines().enumerate() {
-            for (x, bit) in line.chars().enumerate() {
-                let index = y * width + x;
-
-                match bit {
-                    '#' => continue,
-                    '.' => inner.set(index, true),
-                    'S' => {
-                        source = index;
-                        inner.set(index, true)
-                    }
-                    'E' => {
-                        target = index;
-                        inner.set(index, true)
-                    }
-                    _ => panic!("Unknown value '{}'", bit),
-                }
-            }
-        }
+impl Maze {
+    pub fn padded(&self) -> Maze<PaddedGrid<Tile>> {
+        let grid = self.grid.padded();
 
-        Self {
-            inner,
-            width,
-            height,
-            source,
-            target,
+        Maze {
+            source: grid.padded_index(self.source),
+            target: grid.padded_index(self.target),
+            grid,
         }
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let grid = Grid::from_input(input);
+pub fn parse_input(input: &str) -> Result<Maze, ParseError> {
+    let grid = Grid::parse(16, input)?;
 
-    let (best_value, _) = inner(&grid);
+    let Some(source) = grid.find(&Tile::Start) else {
+        return Err(ParseError::missing(16, input, "the start tile 'S'"));
+    };
 
-    best_value
-}
+    let Some(target) = grid.find(&Tile::End) else {
+        return Err(ParseError::missing(16, input, "the end tile 'E'"));
+    };
 
-pub fn part2(input: &str) -> usize {
-    let grid = Grid::from_input(input);
-    let (_, previous) = inner(&grid);
+    Ok(Maze {
+        grid,
+        source,
+        target,
+    })
+}
 
-    let mut visited = FixedBitSet::with_capacity(grid.size());
-    visited.set(grid.target, true);
+const NO_PATH: SolveError = SolveError::new(16, "there is no path from the start to the end tile");
 
-    let mut queue: VecDeque<Position> = VecDeque::new();
+pub fn part1(maze: &Maze) -> Result<usize, SolveError> {
+    lowest_score(maze).ok_or(NO_PATH)
+}
 
-    for direction in Direction::iter() {
-        for previous in &previous[direction.index()][grid.target] {
-            queue.push_back(*previous);
-        }
-    }
+pub fn part2(maze: &Maze) -> Result<usize, SolveError> {
+    best_path_tiles(maze).ok_or(NO_PATH)
+}
 
-    while let Some(current) = queue.pop_front() {
-        visited.set(current.index, true);
+/// Part 1 for any grid layout, `None` if the end cannot be reached.
+pub fn lowest_score<G: Layout<Tile>>(maze: &Maze<G>) -> Option<usize> {
+    search(maze).best()
+}
 
-        for previous in &previous[current.direction.index()][current.index] {
-            queue.push_back(*previous);
-        }
-    }
+/// Part 2 for any grid layout, `None` if the end cannot be reached.
+pub fn best_path_tiles<G: Layout<Tile>>(maze: &Maze<G>) -> Option<usize> {
+    let tiles = tiles_on_best_paths(maze).count_ones(..);
+    (tiles > 0).then_some(tiles)
+}
 
-    visited.count_ones(..)
+/// Indices of the tiles that are part of at least one best path.
+fn tiles_on_best_paths<G: Layout<Tile>>(maze: &Maze<G>) -> FixedBitSet {
+    let mut tiles = FixedBitSet::with_capacity(maze.grid.size());
+    tiles.extend(
+        search(maze)
+            .on_best_paths()
+            .ones()
+            .map(|state| state / Direction::COUNT),
+    );
+
+    tiles
 }
 
-fn inner(grid: &Grid) -> (usize, [Vec<ArrayVec<Position, 3>>; 4]) {
-    // TODO: Do some preprocessing to make large steps in one go
-    let mut heap: BinaryHeap<HeapState> = BinaryHeap::new();
-    heap.push(HeapState {
-        position: Position {
-            index: grid.source,
-            direction: Direction::Right,
-        },
-        previous_position: None,
-        value: 0,
+/// The maze with the tiles on best paths highlighted.
+pub fn render(maze: &Maze) -> Picture {
+    let mut picture = Picture::from_grid(&maze.grid, |tile| match tile {
+        Tile::Wall => Pixel::new('#', Color::GREY),
+        Tile::Open => Pixel::new('.', Color::DARK_GREY),
+        Tile::Start => Pixel::new('S', Color::GREEN),
+        Tile::End => Pixel::new('E', Color::RED),
     });
 
-    let mut best_value = usize::MAX;
+    let best_path = Overlay {
+        background: Color::GOLD,
+        symbol: None,
+    };
+    picture.highlight(tiles_on_best_paths(maze).ones(), best_path);
 
-    let mut best_value_per_position_direction: [Vec<usize>; 4] = [
-        vec![usize::MAX; grid.size()],
-        vec![usize::MAX; grid.size()],
-        vec![usize::MAX; grid.size()],
-        vec![usize::MAX; grid.size()],
-    ];
+    picture
+}
 
-    let mut previous: [Vec<ArrayVec<Position, 3>>; 4] = [
-        vec![ArrayVec::new(); grid.size()],
-        vec![ArrayVec::new(); grid.size()],
-        vec![ArrayVec::new(); grid.size()],
-        vec![ArrayVec::new(); grid.size()],
-    ];
+fn search<G: Layout<Tile>>(maze: &Maze<G>) -> ShortestPaths<Position> {
+    expansions(maze).into_paths()
+}
 
-    while let Some(state) = heap.pop() {
-        let direction_index = state.position.direction.index();
+/// The search behind both parts, one position at a time in order of their lowest score. The first
+/// expansion of the end tile comes with the part 1 answer, and the finished search knows the best
+/// paths of part 2.
+pub fn expansions<G: Layout<Tile>>(
+    maze: &Maze<G>,
+) -> Dijkstra<Position, impl FnMut(Position) -> Moves + '_, impl FnMut(Position) -> bool + '_> {
+    let start = Position {
+        index: maze.source,
+        direction: Direction::Right,
+    };
+
+    Dijkstra::new(
+        [start],
+        maze.grid.size() * Direction::COUNT,
+        |position| {
+            let mut next = Moves::new();
+
+            for direction in [
+                position.direction.turn_left(),
+                position.direction.turn_right(),
+            ] {
+                let turned = Position {
+                    index: position.index,
+                    direction,
+                };
+                next.push((turned, 1000));
+            }
 
-        let current_value =
-            best_value_per_position_direction[direction_index][state.position.index];
+            if let Some(index) = maze.grid.move_towards(position.index, position.direction) {
+                if maze.grid[index] != Tile::Wall {
+                    let forward = Position {
+                        index,
+                        direction: position.direction,
+                    };
+                    next.push((forward, 1));
+                }
+            }
 
-        if current_value < state.value {
-            continue;
-        }
+            next
+        },
+        |position| position.index == maze.target,
+    )
+}
 
-        if best_value < state.value {
+/// A maze of at least `width` by `height` tiles, rounded up to odd numbers. It is carved as a
+/// perfect maze and then opened up in places, so that there are several best paths.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let width = size.width.max(5) | 1;
+    let height = size.height.max(5) | 1;
+    let mut open = vec![false; width * height];
+
+    // Depth first carving between the tiles on odd coordinates
+    let mut stack: Vec<(usize, usize)> = vec![(1, height - 2)];
+    open[(height - 2) * width + 1] = true;
+
+    while let Some(&(x, y)) = stack.last() {
+        let candidates = Direction::iter()
+            .filter_map(|direction| {
+                let (dx, dy) = direction.delta();
+                let next = (x.checked_add_signed(dx * 2)?, y.checked_add_signed(dy * 2)?);
+                (next.0 < width - 1 && next.1 < height - 1 && !open[next.1 * width + next.0])
+                    .then_some((next, direction.delta()))
+            })
+            .collect::<ArrayVec<_, 4>>();
+
+        if candidates.is_empty() {
+            stack.pop();
             continue;
         }
 
-        best_value_per_position_direction[direction_index][state.position.index] = state.value;
+        let ((nx, ny), (dx, dy)) = candidates[rng.usize(..candidates.len())];
+        open[y.wrapping_add_signed(dy) * width + x.wrapping_add_signed(dx)] = true;
+        open[ny * width + nx] = true;
+        stack.push((nx, ny));
+    }
 
-        if let Some(previous_position) = state.previous_position {
-            if previous[state.position.direction.index()][state.position.index]
-                .contains(&previous_position)
-            {
-                continue;
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            if rng.usize(..10) == 0 {
+                open[y * width + x] = true;
             }
-            previous[state.position.direction.index()][state.position.index]
-                .push(previous_position);
         }
+    }
 
-        if state.position.index == grid.target {
-            best_value = state.value;
-            continue;
-        }
+    generate::grid(width, height, |x, y| match (x, y) {
+        _ if (x, y) == (1, height - 2) => 'S',
+        _ if (x, y) == (width - 2, 1) => 'E',
+        _ if open[y * width + x] => '.',
+        _ => '#',
+    })
+}
 
-        let (left, right) = state.position.direction.neighbours();
-
-        heap.push(HeapState {
-            value: state.value + 1000,
-            position: Position {
-                index: state.position.index,
-                direction: left,
-            },
-            previous_position: Some(state.position),
-        });
-        heap.push(HeapState {
-            value: state.value + 1000,
-            position: Position {
-                index: state.position.index,
-                direction: right,
-            },
-            previous_position: Some(state.position),
-        });
-
-        let Some(next_position) =
-            move_towards(state.position.index, state.position.direction, &grid)
-        else {
-            continue;
-        };
+pub struct Day16;
 
-        if !grid.inner.contains(next_position) {
-            continue;
-        }
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Reindeer Maze";
+    const EXAMPLES: &'static [&'static str] =
+        &["examples/2024/day16_1a.txt", "examples/2024/day16_1b.txt"];
+
+    const COUNTERS: &'static [&'static Counter] = &[&HEAP_PUSHES, &HEAP_POPS, &STALE_ENTRIES];
+
+    type Input = Maze;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(part2(input))
+    }
 
-        heap.push(HeapState {
-            value: state.value + 1,
-            position: Position {
-                index: next_position,
-                direction: state.position.direction,
-            },
-            previous_position: Some(state.position),
-        });
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
     }
 
-    (best_value, previous)
+    fn render(input: &Self::Input) -> Option<Picture> {
+        Some(render(input))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse::ErrorKind;
 
     const TEST_INPUT_1A: &str = include_str!("../../examples/2024/day16_1a.txt");
-    const TEST_INPUT_1B: &str = include_str!("../../examples/2024/day16_1b.txt");
+
+    /// The examples on the padded layout, the manifest covers the plain one.
+    #[test]
+    fn test_padded() {
+        assert_eq!(
+            Some(7036),
+            lowest_score(&parse_input(TEST_INPUT_1A).unwrap().padded())
+        );
+        assert_eq!(
+            Some(45),
+            best_path_tiles(&parse_input(TEST_INPUT_1A).unwrap().padded())
+        );
+    }
+
+    #[test]
+    fn test_no_path() {
+        let maze = parse_input("#####\n#S#E#\n#####\n").unwrap();
+
+        assert_eq!(Err(NO_PATH), part1(&maze));
+        assert_eq!(Err(NO_PATH), part2(&maze));
+        assert_eq!(None, lowest_score(&maze.padded()));
+    }
 
     #[test]
-    fn test_part1() {
-        assert_eq!(7036, part1(TEST_INPUT_1A));
-        assert_eq!(11048, part1(TEST_INPUT_1B));
+    fn test_expansions() {
+        let maze = parse_input(TEST_INPUT_1A).unwrap();
+        let mut search = expansions(&maze);
+
+        let first = search.next().unwrap();
+        assert_eq!(
+            (maze.source, Direction::Right),
+            (first.state.index, first.state.direction)
+        );
+
+        let end = search.find(|expansion| expansion.state.index == maze.target);
+        assert_eq!(Some(7036), end.map(|expansion| expansion.cost));
+
+        let mut tiles = FixedBitSet::with_capacity(maze.grid.size());
+        tiles.extend(
+            search
+                .into_paths()
+                .on_best_paths()
+                .ones()
+                .map(|state| state / Direction::COUNT),
+        );
+        assert_eq!(45, tiles.count_ones(..));
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(45, part2(TEST_INPUT_1A));
-        assert_eq!(64, part2(TEST_INPUT_1B));
+    fn test_parse_error() {
+        let error = parse_input("#####\n#S.E#\n#..x#\n").unwrap_err();
+        assert_eq!(
+            (3, 4, Some('x')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("#####\n#S..#\n#####\n").unwrap_err();
+        assert_eq!(ErrorKind::Missing("the end tile 'E'"), error.kind);
+        assert_eq!((4, 1, None), (error.line, error.column, error.character));
     }
 }
@@ -1,9 +1,76 @@
-fn inner(patterns: &[&str], line: &str, index: usize, cache: &mut [Option<usize>]) -> usize {
+use crate::generate::{self, Rng, Size};
+use crate::parse::{lines, ErrorKind, Line};
+use crate::stats::Counter;
+use crate::{ParseError, Solution, SolveError};
+
+static CACHE_HITS: Counter = Counter::new("cache hits");
+static CACHE_MISSES: Counter = Counter::new("cache misses");
+
+const OVERFLOW: SolveError = SolveError::new(19, "the number of arrangements overflows");
+
+#[derive(Debug)]
+pub struct Towels {
+    patterns: Vec<String>,
+    designs: Vec<String>,
+}
+
+fn check_stripes(line: &Line, part: &str) -> Result<(), ParseError> {
+    if part.is_empty() {
+        return Err(line.error_at(part, ErrorKind::Expected("a stripe")));
+    }
+
+    match part.find(|c| !matches!(c, 'w' | 'u' | 'b' | 'r' | 'g')) {
+        Some(offset) => Err(line.error(
+            line.offset_of(part) + offset,
+            ErrorKind::UnexpectedCharacter,
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Towels, ParseError> {
+    let mut lines = lines(19, input);
+
+    let Some(line) = lines.next() else {
+        return Err(ParseError::missing(19, input, "the towel patterns"));
+    };
+
+    let patterns = line
+        .text
+        .split(", ")
+        .map(|pattern| {
+            check_stripes(&line, pattern)?;
+            Ok(pattern.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(line) = lines.next().filter(|line| !line.text.is_empty()) {
+        return Err(line.error(0, ErrorKind::Expected("a blank line")));
+    }
+
+    let designs = lines
+        .filter(|line| !line.text.is_empty())
+        .map(|line| {
+            check_stripes(&line, line.text)?;
+            Ok(line.text.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Towels { patterns, designs })
+}
+
+/// Number of ways to arrange the rest of the design from `index` on, with a cache entry for every
+/// index of the design. Long designs made of short patterns have more arrangements than fit a
+/// `u64`, those give `None`.
+fn inner(patterns: &[String], line: &str, index: usize, cache: &mut [Option<u64>]) -> Option<u64> {
     if let Some(count) = cache[index] {
-        return count;
+        CACHE_HITS.increment();
+        return Some(count);
     }
 
-    let mut possibilities = 0;
+    CACHE_MISSES.increment();
+
+    let mut possibilities: u64 = 0;
 
     for pattern in patterns {
         // TODO: line[index..index+pattern.len()] != pattern
@@ -12,68 +79,138 @@ fn inner(patterns: &[&str], line: &str, index: usize, cache: &mut [Option<usize>
         }
 
         if line.len() == pattern.len() + index {
-            possibilities += 1;
+            possibilities = possibilities.checked_add(1)?;
             continue;
         }
 
-        possibilities += inner(patterns, line, index + pattern.len(), cache);
+        possibilities =
+            possibilities.checked_add(inner(patterns, line, index + pattern.len(), cache)?)?;
     }
 
     cache[index] = Some(possibilities);
 
-    possibilities
+    Some(possibilities)
+}
+
+/// Arrangements of the whole design.
+fn arrangements(patterns: &[String], design: &str) -> Option<u64> {
+    inner(patterns, design, 0, &mut vec![None; design.len() + 1])
+}
+
+pub fn part1(input: &Towels) -> u64 {
+    // Too many arrangements to count is still at least one
+    input
+        .designs
+        .iter()
+        .filter(|design| arrangements(&input.patterns, design) != Some(0))
+        .count() as u64
 }
 
-pub fn part1(input: &str) -> usize {
-    let mut lines = input.lines();
+pub fn part2(input: &Towels) -> Result<u64, SolveError> {
+    input
+        .designs
+        .iter()
+        .try_fold(0_u64, |sum, design| {
+            sum.checked_add(arrangements(&input.patterns, design)?)
+        })
+        .ok_or(OVERFLOW)
+}
 
-    let patterns: Vec<&str> = lines.next().unwrap().split(", ").collect();
+/// `width` distinct towel patterns of up to eight stripes, followed by `count` designs. Most
+/// designs are made of the patterns, the others have a stripe changed and may be impossible.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    const STRIPES: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
 
-    lines.next();
+    let stripes = |rng: &mut Rng, length: usize| {
+        (0..length)
+            .map(|_| STRIPES[rng.usize(..STRIPES.len())])
+            .collect::<String>()
+    };
 
-    lines
-        .filter(|line| {
-            if line.is_empty() {
-                return false;
-            }
+    let mut patterns: Vec<String> = Vec::new();
+    for length in (1..=8).cycle().take(size.width.max(1) * 4) {
+        let pattern = stripes(rng, length);
 
-            inner(&patterns, line, 0, &mut [None; 1000]) > 0
-        })
-        .count()
+        if patterns.len() < size.width.max(1) && !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+    }
+
+    let designs = generate::lines(0..size.count, |_| {
+        let target = rng.usize(20..60);
+        let mut design = String::new();
+
+        while design.len() < target {
+            design.push_str(&patterns[rng.usize(..patterns.len())]);
+        }
+
+        if rng.usize(..3) == 0 {
+            let index = rng.usize(..design.len());
+            design.replace_range(index..=index, &stripes(rng, 1));
+        }
+
+        design
+    });
+
+    format!("{}\n\n{}", patterns.join(", "), designs)
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut lines = input.lines();
+pub struct Day19;
 
-    let patterns: Vec<&str> = lines.next().unwrap().split(", ").collect();
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Linen Layout";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day19.txt"];
 
-    lines.next();
+    const COUNTERS: &'static [&'static Counter] = &[&CACHE_HITS, &CACHE_MISSES];
 
-    lines
-        // .take(1)
-        .map(|line| {
-            if line.is_empty() {
-                return 0;
-            }
+    type Input = Towels;
+    type Output = u64;
 
-            inner(&patterns, line, 0, &mut [None; 1000])
-        })
-        .sum()
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = include_str!("../../examples/2024/day19.txt");
-
     #[test]
-    fn test_part1() {
-        assert_eq!(6, part1(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("r, wr, , b\n\nbrwrr\n").unwrap_err();
+        assert_eq!(
+            (1, 8, Some(',')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("r, wr, b\n\nbrwrr\nbrxr\n").unwrap_err();
+        assert_eq!(
+            (4, 3, Some('x')),
+            (error.line, error.column, error.character)
+        );
     }
 
+    /// Designs of any length are accepted, and a count too large for a `u64` is an error.
     #[test]
-    fn test_part2() {
-        assert_eq!(16, part2(TEST_INPUT));
+    fn test_long_design() {
+        let input = parse_input(&format!("r, rr\n\n{}\n", "r".repeat(2000))).unwrap();
+        assert_eq!(1, part1(&input));
+        assert_eq!(Err(OVERFLOW), part2(&input));
+
+        let input = parse_input(&format!("r\n\n{}\n", "r".repeat(2000))).unwrap();
+        assert_eq!(Ok(1), part2(&input));
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,38 @@
+use crate::generate::{self, Rng, Size};
+use crate::parse::{lines, ErrorKind};
+use crate::{ParseError, Solution, SolveError};
 use itertools::*;
 use std::cmp::Ordering;
 use std::ops::RangeInclusive;
 
 type Output = usize;
 
-pub fn input_generator(input: &str) -> impl Iterator<Item = Vec<u32>> + use<'_> {
-    input.lines().map(|line| {
-        line.split_whitespace()
-            .map(|item| item.parse().unwrap())
-            .collect::<Vec<_>>()
-    })
+/// One report per line, of at least two levels each.
+pub fn input_generator(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    lines(2, input)
+        .map(|line| {
+            let report = line
+                .text
+                .split_whitespace()
+                .map(|item| line.number(item))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if report.len() < 2 {
+                return Err(line.error(line.text.len(), ErrorKind::Expected("a level")));
+            }
+
+            Ok(report)
+        })
+        .collect()
 }
 
 const ALLOWED_RANGE: RangeInclusive<u32> = 1..=3;
 
 fn is_valid_report(report: &[u32]) -> bool {
-    let a = report[0];
-    let b = report[1];
+    // A report with less than two levels, left after removing a level from two, can't be unsafe
+    let [a, b, ..] = *report else {
+        return true;
+    };
     let expected_ordering = a.cmp(&b);
 
     if expected_ordering == Ordering::Equal {
@@ -40,16 +56,18 @@ fn is_valid_report(report: &[u32]) -> bool {
     })
 }
 
-pub fn part1(input: &str) -> Output {
-    input_generator(input)
+pub fn part1(input: &[Vec<u32>]) -> Output {
+    input
+        .iter()
         .filter(|report| is_valid_report(report))
         .count()
 }
 
-pub fn part2(input: &str) -> Output {
+pub fn part2(input: &[Vec<u32>]) -> Output {
     let mut workhorse = Vec::with_capacity(5);
 
-    input_generator(input)
+    input
+        .iter()
         .filter(|report| {
             if is_valid_report(report) {
                 return true;
@@ -79,19 +97,72 @@ pub fn part2(input: &str) -> Output {
         .count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// `count` reports of five to eight levels, most of them safe.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    generate::lines(0..size.count, |_| {
+        let direction = if rng.bool() { 1 } else { -1 };
+        let mut level = rng.i32(40..60);
+
+        (0..rng.usize(5..=8))
+            .map(|_| {
+                let current = level;
+
+                // Mostly safe steps, with the occasional one that breaks the report
+                let step = match rng.u8(..10) {
+                    0 => rng.i32(-3..=5),
+                    _ => rng.i32(1..=3),
+                };
+                level += direction * step;
+
+                current
+            })
+            .join(" ")
+    })
+}
 
-    const TEST_INPUT: &str = include_str!("../../examples/2024/day2.txt");
+pub struct Day2;
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(2, part1(TEST_INPUT));
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day2.txt"];
+
+    type Input = Vec<Vec<u32>>;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_generator(input)
     }
 
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
-    fn test_part2() {
-        assert_eq!(4, part2(TEST_INPUT));
+    fn test_parse_error() {
+        let error = input_generator("7 6 4\n1 2 -7\n").unwrap_err();
+        assert_eq!(
+            (2, 5, Some('-')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = input_generator("7 6 4 2 1\n\n1 2 7 8 9\n").unwrap_err();
+        assert_eq!((2, 1, None), (error.line, error.column, error.character));
+
+        let error = input_generator("7 6 4 2 1\n13\n").unwrap_err();
+        assert_eq!((2, 3, None), (error.line, error.column, error.character));
     }
 }
@@ -1,6 +1,15 @@
-use itertools::{Either, Itertools};
+use crate::generate::{Rng, Size};
+use crate::parse::{lines, ErrorKind, Line};
+use crate::{ParseError, Solution, SolveError};
+use itertools::Itertools;
 
-fn get_heights<'a>(lines: impl Iterator<Item = &'a str>) -> [u8; 5] {
+/// Rows of a schematic, the top and bottom one included.
+const ROWS: usize = 7;
+
+type Heights = [u8; 5];
+type Input = (Vec<Heights>, Vec<Heights>);
+
+fn get_heights<'a>(lines: impl Iterator<Item = &'a str>) -> Heights {
     let mut element = [0; 5];
 
     lines
@@ -15,17 +24,62 @@ fn get_heights<'a>(lines: impl Iterator<Item = &'a str>) -> [u8; 5] {
     element
 }
 
-pub fn part1(input: &str) -> usize {
-    let (locks, keys): (Vec<[u8; 5]>, Vec<[u8; 5]>) = input.split("\n\n").partition_map(|input| {
-        let mut is_key = !input.starts_with("#");
+fn check_row(line: &Line) -> Result<(), ParseError> {
+    for (x, c) in line.text.char_indices() {
+        if x == 5 || !matches!(c, '#' | '.') {
+            return Err(line.error(x, ErrorKind::UnexpectedCharacter));
+        }
+    }
+
+    if line.text.len() < 5 {
+        return Err(line.error(line.text.len(), ErrorKind::UnexpectedEndOfLine));
+    }
 
-        if is_key {
-            Either::Right(get_heights(input.lines().rev()))
+    Ok(())
+}
+
+/// Splits the schematics, of seven rows each, into the heights of the locks and the keys.
+pub fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let lines = lines(25, input).collect::<Vec<_>>();
+
+    let mut locks = Vec::new();
+    let mut keys = Vec::new();
+
+    for schematic in lines
+        .split(|line| line.text.is_empty())
+        .filter(|schematic| !schematic.is_empty())
+    {
+        for line in schematic {
+            check_row(line)?;
+        }
+
+        if let Some(line) = schematic.get(ROWS) {
+            return Err(line.error(0, ErrorKind::Expected("a blank line")));
+        }
+
+        if let Some(last) = schematic.last().filter(|_| schematic.len() < ROWS) {
+            return Err(ParseError::new(
+                25,
+                last.index + 1,
+                0,
+                None,
+                ErrorKind::Expected("a schematic row"),
+            ));
+        }
+
+        let rows = schematic.iter().map(|line| line.text);
+
+        if schematic[0].text.starts_with('#') {
+            locks.push(get_heights(rows));
         } else {
-            Either::Left(get_heights(input.lines()))
+            keys.push(get_heights(rows.rev()));
         }
-    });
+    }
+
+    Ok((locks, keys))
+}
 
+pub fn part1((locks, keys): &Input) -> usize {
     locks
         .iter()
         .cartesian_product(keys)
@@ -39,14 +93,69 @@ pub fn part1(input: &str) -> usize {
         .count()
 }
 
+/// `count` schematics of locks and keys with random pin heights.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let schematics = (0..size.count).map(|_| {
+        let heights: Heights = std::array::from_fn(|_| rng.u8(..=5));
+        let is_lock = rng.bool();
+
+        (0..ROWS)
+            .map(|row| {
+                // Locks fill up from the top, keys from the bottom
+                let depth = if is_lock { row } else { ROWS - 1 - row };
+
+                heights
+                    .iter()
+                    .map(|height| if depth <= *height as usize { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n")
+    });
+
+    format!("{}\n", schematics.collect::<Vec<_>>().join("\n\n"))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Code Chronicle";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day25.txt"];
+
+    const HAS_PART2: bool = false;
+
+    type Input = Input;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = include_str!("../../examples/2024/day25.txt");
-
     #[test]
-    fn test_part1() {
-        assert_eq!(3, part1(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("#####\n.####\n.#.#\n").unwrap_err();
+        assert_eq!((3, 5, None), (error.line, error.column, error.character));
+
+        let error = parse_input(&"#####\n".repeat(300)).unwrap_err();
+        assert_eq!(
+            (8, 1, Some('#')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("#####\n.....\n\n#####\n").unwrap_err();
+        assert_eq!((3, 1, None), (error.line, error.column, error.character));
     }
 }
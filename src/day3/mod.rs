@@ -1,50 +1,117 @@
+use crate::generate::{Rng, Size};
+use crate::{ParseError, Solution, SolveError};
 use regex::Regex;
 
 type Output = usize;
 
-fn parse_multiplication(input: &str) -> Output {
-    let (left, right) = input
-        .strip_prefix("mul(")
-        .unwrap()
-        .strip_suffix(")")
-        .unwrap()
-        .split_once(",")
-        .unwrap();
-
-    left.parse::<usize>().unwrap() * right.parse::<usize>().unwrap()
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Multiply(usize, usize),
+    Do,
+    Dont,
 }
 
-pub fn part1(input: &str) -> Output {
-    let regex = Regex::new(r"(mul\(\d*,\d*\))").unwrap();
-    regex
+/// Extracts the instructions from the corrupted memory. Everything else is noise by definition,
+/// so this can't fail. The operands of a multiplication have one to three digits, as the puzzle
+/// describes, so `mul(1234,5)` and `mul(,5)` are noise as well.
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)").unwrap();
+
+    Ok(regex
         .captures_iter(input)
-        .map(|capture| parse_multiplication(&capture[0]))
+        .map(|capture| match &capture[0] {
+            "do()" => Instruction::Do,
+            "don't()" => Instruction::Dont,
+            _ => Instruction::Multiply(capture[1].parse().unwrap(), capture[2].parse().unwrap()),
+        })
+        .collect())
+}
+
+pub fn part1(input: &[Instruction]) -> Output {
+    input
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::Multiply(left, right) => left * right,
+            _ => 0,
+        })
         .sum()
 }
 
-pub fn part2(input: &str) -> Output {
-    let regex = Regex::new(r"(mul\(\d*,\d*\))|(do\(\))|(don\'t\(\))").unwrap();
-    let (_, sum) = regex
-        .captures_iter(input)
-        .fold((true, 0), |(enabled, acc), capture| {
-            let capture = &capture[0];
+pub fn part2(input: &[Instruction]) -> Output {
+    let (_, sum) = input
+        .iter()
+        .fold((true, 0), |(enabled, acc), instruction| match instruction {
+            Instruction::Do => (true, acc),
+            Instruction::Dont => (false, acc),
+            Instruction::Multiply(_, _) if !enabled => (false, acc),
+            Instruction::Multiply(left, right) => (true, acc + left * right),
+        });
 
-            if capture.starts_with("do(") {
-                return (true, acc);
-            }
+    sum
+}
 
-            if capture.starts_with("don") {
-                return (false, acc);
-            }
+/// Corrupted memory holding `count` instructions and bits of noise, split over several lines.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    const NOISE: &[&str] = &[
+        "%&",
+        "mul[3,7]",
+        "!@^",
+        "mul ( 2 , 4 )",
+        "select()",
+        "from(",
+        "mul(4*",
+        ")?",
+        "how()",
+        "mul(1234,5)",
+        "don't",
+        "do(",
+    ];
 
-            if !enabled {
-                return (false, acc);
-            }
+    let mut input = String::new();
 
-            (true, acc + parse_multiplication(capture))
-        });
+    for index in 0..size.count {
+        match rng.u8(..10) {
+            0 => input.push_str("do()"),
+            1 => input.push_str("don't()"),
+            2..=5 => input.push_str(&format!("mul({},{})", rng.u16(1..1000), rng.u16(1..1000))),
+            _ => input.push_str(NOISE[rng.usize(..NOISE.len())]),
+        }
 
-    sum
+        if index % 64 == 63 {
+            input.push('\n');
+        }
+    }
+
+    input.push('\n');
+    input
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+    const EXAMPLES: &'static [&'static str] =
+        &["examples/2024/day3.txt", "examples/2024/day3_part_2.txt"];
+
+    type Input = Vec<Instruction>;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
 #[cfg(test)]
@@ -52,18 +119,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1() {
-        assert_eq!(
-            161,
-            part1("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))")
-        );
-    }
-
-    #[test]
-    fn test_part2() {
+    fn test_operands() {
         assert_eq!(
-            48,
-            part2("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))")
+            vec![Instruction::Multiply(123, 4)],
+            parse_input("mul(1234,5)mul(,5)mul(5,)mul(123,4)").unwrap()
         );
     }
 }
@@ -1,14 +1,19 @@
+use crate::direction::Compass;
+use crate::generate::{self, Rng, Size};
+use crate::grid::{Grid, Layout, Sentinel};
+use crate::{ParseError, Solution, SolveError};
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
 
 type Output = usize;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
-enum XMAS {
+pub enum XMAS {
     X,
     M,
     A,
     S,
+    /// Padding around a [`crate::grid::PaddedGrid`], never part of a word.
+    Border,
 }
 
 impl XMAS {
@@ -17,114 +22,49 @@ impl XMAS {
             XMAS::X => Some(XMAS::M),
             XMAS::M => Some(XMAS::A),
             XMAS::A => Some(XMAS::S),
-            XMAS::S => None,
+            XMAS::S | XMAS::Border => None,
         }
     }
 }
 
-#[derive(EnumIter, Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    UpRight,
-    Right,
-    DownRight,
-    Down,
-    DownLeft,
-    Left,
-    UpLeft,
+impl Sentinel for XMAS {
+    const SENTINEL: Self = XMAS::Border;
 }
 
-#[derive(Debug)]
-struct Grid {
-    inner: Vec<XMAS>,
-    width: usize,
-    height: usize,
-}
-
-impl Grid {
-    fn from_input(input: &str) -> Self {
-        let inner = input
-            .lines()
-            .flat_map(|line| {
-                line.chars().map(|c| match c {
-                    'X' => XMAS::X,
-                    'M' => XMAS::M,
-                    'A' => XMAS::A,
-                    'S' => XMAS::S,
-                    _ => panic!("Unknown character '{}'", c),
-                })
-            })
-            .collect::<Vec<_>>();
+impl TryFrom<char> for XMAS {
+    type Error = ();
 
-        let width = input.lines().next().unwrap().len();
-        let height = inner.len() / width;
-
-        Self {
-            inner,
-            width,
-            height,
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'X' => Ok(XMAS::X),
+            'M' => Ok(XMAS::M),
+            'A' => Ok(XMAS::A),
+            'S' => Ok(XMAS::S),
+            _ => Err(()),
         }
     }
+}
 
-    fn at(&self, index: usize) -> XMAS {
-        self.inner[index]
-    }
-
-    fn move_towards(&self, index: usize, direction: Direction) -> Option<usize> {
-        match direction {
-            Direction::Up => {
-                if index < self.width {
-                    return None;
-                }
-
-                Some(index - self.width)
-            }
-            Direction::Down => {
-                if index >= (self.height - 1) * self.width {
-                    return None;
-                }
-
-                Some(index + self.width)
-            }
-            Direction::Left => {
-                if index.rem_euclid(self.width) == 0 {
-                    return None;
-                }
-
-                Some(index - 1)
-            }
-            Direction::Right => {
-                if index.rem_euclid(self.width) == self.width - 1 {
-                    return None;
-                }
+pub fn parse_input(input: &str) -> Result<Grid<XMAS>, ParseError> {
+    Grid::parse(4, input)
+}
 
-                Some(index + 1)
-            }
-            Direction::UpRight => {
-                self.move_towards(self.move_towards(index, Direction::Up)?, Direction::Right)
-            }
-            Direction::DownRight => {
-                self.move_towards(self.move_towards(index, Direction::Down)?, Direction::Right)
-            }
-            Direction::DownLeft => {
-                self.move_towards(self.move_towards(index, Direction::Down)?, Direction::Left)
-            }
-            Direction::UpLeft => {
-                self.move_towards(self.move_towards(index, Direction::Up)?, Direction::Left)
-            }
-        }
-    }
+pub fn part1(grid: &Grid<XMAS>) -> Output {
+    count_xmas(grid)
 }
 
-pub fn part1(input: &str) -> Output {
-    let grid = Grid::from_input(input);
+pub fn part2(grid: &Grid<XMAS>) -> Output {
+    count_x_mas(grid)
+}
 
-    grid.inner
+/// Part 1 for any grid layout.
+pub fn count_xmas(grid: &impl Layout<XMAS>) -> Output {
+    grid.cells()
         .iter()
         .enumerate()
         .filter(|(_, element)| **element == XMAS::X)
         .map(|(index, _)| {
-            Direction::iter()
+            Compass::iter()
                 .filter(|direction| {
                     let mut current_index = index;
                     let mut current_element = XMAS::X;
@@ -135,7 +75,7 @@ pub fn part1(input: &str) -> Output {
                             return false;
                         };
 
-                        if grid.at(next_index) != target_element {
+                        if grid[next_index] != target_element {
                             return false;
                         }
 
@@ -150,31 +90,30 @@ pub fn part1(input: &str) -> Output {
         .sum()
 }
 
-pub fn part2(input: &str) -> Output {
-    let grid = Grid::from_input(input);
-
-    grid.inner
+/// Part 2 for any grid layout.
+pub fn count_x_mas(grid: &impl Layout<XMAS>) -> Output {
+    grid.cells()
         .iter()
         .enumerate()
         .filter(|(_, element)| **element == XMAS::A)
         .filter_map(|(index, _)| {
-            let top_left = grid.at(grid.move_towards(index, Direction::UpLeft)?);
-            if top_left == XMAS::A || top_left == XMAS::X {
+            let top_left = grid[grid.move_towards(index, Compass::UpLeft)?];
+            if !matches!(top_left, XMAS::M | XMAS::S) {
                 return None;
             }
 
-            let bottom_right = grid.at(grid.move_towards(index, Direction::DownRight)?);
-            if bottom_right == XMAS::A || bottom_right == XMAS::X || bottom_right == top_left {
+            let bottom_right = grid[grid.move_towards(index, Compass::DownRight)?];
+            if !matches!(bottom_right, XMAS::M | XMAS::S) || bottom_right == top_left {
                 return None;
             }
 
-            let top_right = grid.at(grid.move_towards(index, Direction::UpRight)?);
-            if top_right == XMAS::A || top_right == XMAS::X {
+            let top_right = grid[grid.move_towards(index, Compass::UpRight)?];
+            if !matches!(top_right, XMAS::M | XMAS::S) {
                 return None;
             }
 
-            let bottom_left = grid.at(grid.move_towards(index, Direction::DownLeft)?);
-            if bottom_left == XMAS::A || bottom_left == XMAS::X || bottom_left == top_right {
+            let bottom_left = grid[grid.move_towards(index, Compass::DownLeft)?];
+            if !matches!(bottom_left, XMAS::M | XMAS::S) || bottom_left == top_right {
                 return None;
             }
 
@@ -183,19 +122,62 @@ pub fn part2(input: &str) -> Output {
         .count()
 }
 
+/// A `width` by `height` word search of random letters.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    const LETTERS: [char; 4] = ['X', 'M', 'A', 'S'];
+
+    generate::grid(size.width, size.height, |_, _| LETTERS[rng.usize(..4)])
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day4.txt"];
+
+    type Input = Grid<XMAS>;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const TEST_INPUT: &str = include_str!("../../examples/2024/day4.txt");
 
+    /// The examples on the padded layout, the manifest covers the plain one.
     #[test]
-    fn test_part1() {
-        assert_eq!(18, part1(TEST_INPUT));
+    fn test_padded() {
+        assert_eq!(18, count_xmas(&parse_input(TEST_INPUT).unwrap().padded()));
+        assert_eq!(9, count_x_mas(&parse_input(TEST_INPUT).unwrap().padded()));
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(9, part2(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("XMAS\nXMQS\n").unwrap_err();
+        assert_eq!(
+            (2, 3, Some('Q')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("XMAS\nXMA\n").unwrap_err();
+        assert_eq!((2, 4, None), (error.line, error.column, error.character));
     }
 }
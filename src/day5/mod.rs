@@ -1,8 +1,16 @@
+use crate::generate::{self, Rng, Size};
+use crate::parse::{lines, ErrorKind, Line};
+use crate::stats::Counter;
+use crate::{ParseError, Solution, SolveError};
+use itertools::Itertools;
+
 type Element = u8;
-type Output = u16;
+type Output = u64;
+
+static SWAPS: Counter = Counter::new("swaps");
 
 #[derive(Copy, Clone, Debug)]
-struct BitSet {
+pub struct BitSet {
     inner: u128,
 }
 
@@ -15,6 +23,10 @@ impl BitSet {
         self.inner = self.inner | (1 << n)
     }
 
+    pub fn remove(&mut self, n: u8) {
+        self.inner &= !(1 << n)
+    }
+
     pub fn is_set(&self, n: u8) -> bool {
         (self.inner >> n) & 1 > 0
     }
@@ -45,36 +57,104 @@ impl Iterator for BitSetIterator {
     }
 }
 
-fn parse_input(input: &str) -> ([BitSet; 100], Vec<Vec<Element>>) {
-    let (rules_input, updates) = input.split_once("\n\n").unwrap();
+type Input = ([BitSet; 100], Vec<Vec<Element>>);
+
+/// Pages are numbered with two digits, which the rules lookup table and the bitsets rely on.
+const MAX_PAGE: Element = 99;
+
+fn parse_page(line: &Line, part: &str) -> Result<Element, ParseError> {
+    let page = line.number::<Element>(part)?;
+
+    if page > MAX_PAGE {
+        return Err(line.error_at(part, ErrorKind::NumberTooLarge));
+    }
+
+    Ok(page)
+}
+
+/// The solvers rely on the rules putting the pages of every update in a single order, so rules
+/// between its pages may not form a cycle. Points at the first page that cannot be placed.
+fn check_order(
+    line: &Line,
+    rules: &[BitSet; 100],
+    parts: &[&str],
+    update: &[Element],
+) -> Result<(), ParseError> {
+    let mut remaining = BitSet::zeros();
+    for page in update {
+        remaining.insert(*page);
+    }
+
+    while remaining.inner != 0 {
+        // A page that none of the remaining pages has to precede can be placed next
+        let next = BitSetIterator::new(remaining.inner)
+            .find(|page| rules[*page as usize].inner & remaining.inner == 0);
+
+        match next {
+            Some(page) => remaining.remove(page),
+            None => {
+                let index = update
+                    .iter()
+                    .position(|page| remaining.is_set(*page))
+                    .expect("remaining pages are part of the update");
+
+                return Err(line.error_at(
+                    parts[index],
+                    ErrorKind::Expected("a page the rules put in order"),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut lines = lines(5, input);
 
     let mut rules: [BitSet; 100] = [BitSet::zeros(); 100];
 
-    rules_input
-        .lines()
-        .map(|line| {
-            let (a, b) = line.split_once("|").unwrap();
-            (b.parse::<Element>().unwrap(), a.parse::<Element>().unwrap())
-        })
-        .for_each(|(b, a)| {
-            rules[b as usize].insert(a);
-        });
+    for line in lines.by_ref().take_while(|line| !line.text.is_empty()) {
+        let (a, b) = line.split_once("|")?;
+        let a = parse_page(&line, a)?;
+        let b = parse_page(&line, b)?;
+
+        rules[b as usize].insert(a);
+    }
 
-    let updates = updates
-        .lines()
+    let updates = lines
         .map(|line| {
-            line.split(',')
-                .map(|element| element.parse::<Element>().unwrap())
-                .collect::<Vec<_>>()
+            let parts = line.text.split(',').collect::<Vec<_>>();
+            let mut update = Vec::with_capacity(parts.len());
+            let mut included = BitSet::zeros();
+
+            for part in &parts {
+                let page = parse_page(&line, part)?;
+
+                if included.is_set(page) {
+                    return Err(
+                        line.error_at(part, ErrorKind::Expected("a page not yet in the update"))
+                    );
+                }
+
+                included.insert(page);
+                update.push(page);
+            }
+
+            check_order(&line, &rules, &parts, &update)?;
+
+            Ok(update)
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (rules, updates)
-}
+    if updates.is_empty() {
+        return Err(ParseError::missing(5, input, "the updates"));
+    }
 
-pub fn part1(input: &str) -> Output {
-    let (rules, updates) = parse_input(input);
+    Ok((rules, updates))
+}
 
+pub fn part1((rules, updates): &Input) -> Output {
     updates
         .iter()
         .filter(|update| {
@@ -106,63 +186,193 @@ pub fn part1(input: &str) -> Output {
         .sum()
 }
 
-pub fn part2(input: &str) -> Output {
-    let (rules, mut updates) = parse_input(input);
-    let mut index_of_element: [Option<u8>; 100] = [None; 100];
+pub fn part2(input: &Input) -> Output {
+    let mut reordering = swaps(input);
+    let mut changed = vec![false; input.1.len()];
 
-    updates
-        .iter_mut()
-        .filter_map(|update| {
-            index_of_element = [None; 100];
+    for swap in reordering.by_ref() {
+        changed[swap.update] = true;
+    }
 
-            for (index, element) in update.iter().enumerate() {
-                index_of_element[*element as usize] = Some(index as u8);
-            }
+    reordering
+        .updates()
+        .iter()
+        .zip(changed)
+        .filter(|(_, changed)| *changed)
+        .map(|(update, _)| update[update.len() / 2] as Output)
+        .sum()
+}
+
+/// Two pages of an update swapped by part 2, because a rule puts `other` before `page`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Swap {
+    /// Index of the update in the input.
+    pub update: usize,
+    pub page: Element,
+    /// Position of `page` before the swap, which `other` moves to.
+    pub index: usize,
+    pub other: Element,
+    /// Position of `other` before the swap, which `page` moves to.
+    pub other_index: usize,
+}
+
+/// Part 2 one swap at a time, over copies of the updates.
+pub fn swaps((rules, updates): &Input) -> Reordering<'_> {
+    let mut reordering = Reordering {
+        rules,
+        updates: updates.clone(),
+        update: 0,
+        index: 0,
+        index_of_element: [None; 100],
+    };
+    reordering.start_update();
+
+    reordering
+}
+
+pub struct Reordering<'a> {
+    rules: &'a [BitSet; 100],
+    updates: Vec<Vec<Element>>,
+    update: usize,
+    index: u8,
+    index_of_element: [Option<u8>; 100],
+}
 
-            let mut changed = false;
-            let mut index: u8 = 0;
+impl Reordering<'_> {
+    /// The updates with every swap so far applied, all in the right order once drained.
+    pub fn updates(&self) -> &[Vec<Element>] {
+        &self.updates
+    }
+
+    fn start_update(&mut self) {
+        self.index = 0;
+        self.index_of_element = [None; 100];
+
+        let Some(update) = self.updates.get(self.update) else {
+            return;
+        };
+
+        for (index, element) in update.iter().enumerate() {
+            self.index_of_element[*element as usize] = Some(index as u8);
+        }
+    }
+}
+
+impl Iterator for Reordering<'_> {
+    type Item = Swap;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(update) = self.updates.get_mut(self.update) {
             let target = update.len() as u8;
 
-            while index < target {
+            while self.index < target {
+                let index = self.index;
                 let element = update[index as usize];
 
-                let rule = rules[element as usize];
+                let rule = self.rules[element as usize];
 
                 if rule.inner == 0 {
-                    index += 1;
+                    self.index += 1;
                     continue;
                 }
 
                 let minimum_index = BitSetIterator::new(rule.inner)
-                    .into_iter()
-                    .filter_map(|element| Some((index_of_element[element as usize]?, element)))
+                    .filter_map(|element| Some((self.index_of_element[element as usize]?, element)))
                     .max_by_key(|(index, _)| *index);
 
                 let Some((minimum_index, other_element)) = minimum_index else {
-                    index += 1;
+                    self.index += 1;
                     continue;
                 };
 
                 if index > minimum_index {
-                    index += 1;
+                    self.index += 1;
                     continue;
                 }
 
-                changed = true;
-                index_of_element[element as usize] = Some(minimum_index);
+                SWAPS.increment();
+                self.index_of_element[element as usize] = Some(minimum_index);
                 update[minimum_index as usize] = element;
 
-                index_of_element[other_element as usize] = Some(index);
+                self.index_of_element[other_element as usize] = Some(index);
                 update[index as usize] = other_element;
-            }
 
-            if !changed {
-                return None;
+                return Some(Swap {
+                    update: self.update,
+                    page: element,
+                    index: index as usize,
+                    other: other_element,
+                    other_index: minimum_index as usize,
+                });
             }
 
-            Some(update[update.len() / 2] as Output)
-        })
-        .sum()
+            self.update += 1;
+            self.start_update();
+        }
+
+        None
+    }
+}
+
+/// Rules ordering every pair of 49 pages, like the real input, followed by `count` updates of
+/// which about half are in the right order.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let mut pages = (10..=MAX_PAGE).collect::<Vec<_>>();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut input = String::new();
+
+    for (index, before) in pages.iter().enumerate() {
+        for after in &pages[index + 1..] {
+            input.push_str(&format!("{}|{}\n", before, after));
+        }
+    }
+
+    input.push('\n');
+
+    input.push_str(&generate::lines(0..size.count, |_| {
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(rng.usize(2..=11) * 2 + 1);
+
+        if rng.bool() {
+            update.sort_by_key(|page| pages.iter().position(|other| other == page));
+        }
+
+        update.iter().join(",")
+    }));
+
+    input
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day5.txt"];
+
+    const COUNTERS: &'static [&'static Counter] = &[&SWAPS];
+
+    type Input = Input;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
 #[cfg(test)]
@@ -172,12 +382,62 @@ mod tests {
     const TEST_INPUT: &str = include_str!("../../examples/2024/day5.txt");
 
     #[test]
-    fn test_part1() {
-        assert_eq!(143, part1(TEST_INPUT));
+    fn test_swaps() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        let trace = swaps(&input).collect::<Vec<_>>();
+
+        // 75,97,47,61,53 becomes 97,75,47,61,53 in a single swap
+        let first = Swap {
+            update: 3,
+            page: 75,
+            index: 0,
+            other: 97,
+            other_index: 1,
+        };
+        assert_eq!(Some(&first), trace.first());
+
+        // Replaying the trace on the updates gives the part 2 answer
+        let mut updates = input.1.clone();
+        for swap in &trace {
+            updates[swap.update].swap(swap.index, swap.other_index);
+        }
+
+        let middles = trace
+            .iter()
+            .map(|swap| swap.update)
+            .dedup()
+            .map(|update| updates[update][updates[update].len() / 2] as Output);
+        assert_eq!(part2(&input), middles.sum::<Output>());
+        assert_eq!(123, part2(&input));
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(123, part2(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("47|53\n97|130\n\n75,47\n").unwrap_err();
+        assert_eq!(
+            (2, 4, Some('1')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("47|53\n\n75,47\n61,,29\n").unwrap_err();
+        assert_eq!(
+            (4, 4, Some(',')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("47|53\n97|13\n").unwrap_err();
+        assert_eq!((3, 1, None), (error.line, error.column, error.character));
+
+        let error = parse_input("47|53\n\n75,47,75\n").unwrap_err();
+        assert_eq!(
+            (3, 7, Some('7')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("47|53\n53|29\n29|47\n\n75,53,29,47\n").unwrap_err();
+        assert_eq!(
+            (5, 4, Some('5')),
+            (error.line, error.column, error.character)
+        );
     }
 }
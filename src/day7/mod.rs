@@ -1,48 +1,90 @@
+use crate::generate::{self, Rng, Size};
+use crate::parse::{lines, ErrorKind};
+use crate::stats::Counter;
+use crate::{ParseError, Solution, SolveError};
+use itertools::Itertools;
 use std::collections::VecDeque;
 
-pub fn solve_with_operations(input: &str, allow_concatenation: bool) -> usize {
-    let mut queue = VecDeque::new();
+/// The search tries every combination of operators, which grows exponentially with the numbers
+/// of an equation. The puzzle input has at most twelve.
+const MAX_NUMBERS: usize = 12;
 
-    input
-        .lines()
-        .filter_map(|line| {
-            let (test, numbers) = line.split_once(": ").unwrap();
-            let test = test.parse::<usize>().unwrap();
+static QUEUE_PUSHES: Counter = Counter::new("queue pushes");
+
+/// Wide enough for the sum of any number of test values.
+type Output = u128;
+
+#[derive(Debug)]
+pub struct Equation {
+    test: u64,
+    numbers: Vec<u64>,
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Equation>, ParseError> {
+    lines(7, input)
+        .map(|line| {
+            let (test, numbers) = line.split_once(": ")?;
+            let test = line.number(test)?;
             let numbers = numbers
                 .split_whitespace()
-                .map(|number| number.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
+                .map(|number| line.number::<u64>(number))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if numbers.len() > MAX_NUMBERS {
+                let extra = line.text.split_whitespace().nth(MAX_NUMBERS + 1).unwrap();
+                return Err(line.error_at(extra, ErrorKind::TooLarge));
+            }
+
+            if numbers.is_empty() {
+                return Err(line.error(line.text.len(), ErrorKind::Expected("a number")));
+            }
+
+            Ok(Equation { test, numbers })
+        })
+        .collect()
+}
+
+pub fn solve_with_operations(input: &[Equation], allow_concatenation: bool) -> Output {
+    let mut queue = VecDeque::new();
+
+    input
+        .iter()
+        .filter_map(|Equation { test, numbers }| {
+            let test = *test;
 
             queue.clear();
+            QUEUE_PUSHES.increment();
             queue.push_front((numbers[0], 1));
 
             while let Some((total, index)) = queue.pop_back() {
                 if index == numbers.len() {
                     if total == test {
-                        return Some(total);
+                        return Some(total as Output);
                     } else {
                         continue;
                     }
                 }
 
                 let next_number = numbers[index];
-                let next = total + next_number;
-                if next <= test {
-                    queue.push_back((next, index + 1));
-                }
+                let concatenated = match allow_concatenation {
+                    true => 10_u64
+                        .checked_pow(next_number.checked_ilog10().unwrap_or(0) + 1)
+                        .and_then(|shift| total.checked_mul(shift))
+                        .and_then(|shifted| shifted.checked_add(next_number)),
+                    false => None,
+                };
 
-                let next = total * next_number;
-                if next <= test {
-                    queue.push_back((next, index + 1));
-                }
-
-                if !allow_concatenation {
-                    continue;
-                }
+                let operations = [
+                    total.checked_add(next_number),
+                    total.checked_mul(next_number),
+                    concatenated,
+                ];
 
-                let next = total * 10_usize.pow(next_number.ilog10() + 1) + next_number;
-                if next <= test {
-                    queue.push_back((next, index + 1));
+                for next in operations.into_iter().flatten() {
+                    if next <= test {
+                        QUEUE_PUSHES.increment();
+                        queue.push_back((next, index + 1));
+                    }
                 }
             }
 
@@ -51,27 +93,88 @@ pub fn solve_with_operations(input: &str, allow_concatenation: bool) -> usize {
         .sum()
 }
 
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &[Equation]) -> Output {
     solve_with_operations(input, false)
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &[Equation]) -> Output {
     solve_with_operations(input, true)
 }
 
+/// `count` equations of two to nine operands, about half of which can be made true.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    generate::lines(0..size.count, |_| {
+        let numbers = (0..rng.usize(2..=9))
+            .map(|_| rng.u64(1..100))
+            .collect::<Vec<_>>();
+
+        let mut test = numbers[0];
+        for number in &numbers[1..] {
+            let next = match rng.u8(..3) {
+                0 => test.checked_mul(*number),
+                1 => format!("{}{}", test, number).parse().ok(),
+                _ => None,
+            };
+
+            test = next.unwrap_or(test + number);
+        }
+
+        if rng.bool() {
+            test += rng.u64(1..10);
+        }
+
+        format!("{}: {}", test, numbers.iter().join(" "))
+    })
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day7.txt"];
+
+    const COUNTERS: &'static [&'static Counter] = &[&QUEUE_PUSHES];
+
+    type Input = Vec<Equation>;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = include_str!("../../examples/2024/day7.txt");
-
     #[test]
-    fn test_part1() {
-        assert_eq!(3749, part1(TEST_INPUT));
-    }
+    fn test_parse_error() {
+        let error = parse_input("190: 10 19\n3267 81 40 27\n").unwrap_err();
+        assert_eq!(
+            (2, 5, Some(' ')),
+            (error.line, error.column, error.character)
+        );
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(11387, part2(TEST_INPUT));
+        let error = parse_input("190: 10 19\n3267: \n").unwrap_err();
+        assert_eq!((2, 7, None), (error.line, error.column, error.character));
+
+        let error = parse_input("13: 1 1 1 1 1 1 1 1 1 1 1 1 1\n").unwrap_err();
+        assert_eq!(
+            (1, 29, Some('1'), ErrorKind::TooLarge),
+            (error.line, error.column, error.character, error.kind)
+        );
     }
 }
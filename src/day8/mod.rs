@@ -1,30 +1,54 @@
+use crate::generate::{self, Rng, Size};
+use crate::parse::{lines, ErrorKind};
+use crate::render::{Color, Overlay, Picture, Pixel};
+use crate::{ParseError, Solution, SolveError};
 use itertools::*;
 use rustc_hash::FxHashSet;
 use std::collections::HashMap;
 
-fn parse_input(input: &str) -> (isize, isize, HashMap<char, Vec<(isize, isize)>>) {
-    let mut width: isize = 0;
+type Input = (isize, isize, HashMap<char, Vec<(isize, isize)>>);
+
+pub fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
     let mut height: isize = 0;
 
-    let antennas = input
-        .lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            height += 1;
+    let mut antennas: HashMap<char, Vec<(isize, isize)>> = HashMap::new();
+
+    for line in lines(8, input) {
+        height += 1;
+
+        for (x, element) in line.text.char_indices() {
+            if x == width || !(element == '.' || element.is_ascii_alphanumeric()) {
+                return Err(line.error(x, ErrorKind::UnexpectedCharacter));
+            }
+
+            if element != '.' {
+                antennas
+                    .entry(element)
+                    .or_default()
+                    .push((x as isize, line.index as isize));
+            }
+        }
+
+        if line.text.len() < width {
+            return Err(line.error(line.text.len(), ErrorKind::UnexpectedEndOfLine));
+        }
+    }
 
-            width = line.len() as isize;
+    Ok((width as isize, height, antennas))
+}
 
-            line.char_indices()
-                .filter(|(_, element)| *element != '.')
-                .map(move |(x, element)| (element, (x as isize, y as isize)))
-        })
-        .into_group_map();
+pub fn part1(input: &Input) -> usize {
+    antinodes(input).len()
+}
 
-    (width, height, antennas)
+pub fn part2(input: &Input) -> usize {
+    resonant_antinodes(input).len()
 }
 
-pub fn part1(input: &str) -> usize {
-    let (width, height, antennas) = parse_input(input);
+/// Locations twice as far from one antenna as from another one of the same frequency.
+fn antinodes((width, height, antennas): &Input) -> FxHashSet<(isize, isize)> {
+    let (width, height) = (*width, *height);
 
     let mut antinodes: FxHashSet<(isize, isize)> = FxHashSet::default();
 
@@ -49,11 +73,12 @@ pub fn part1(input: &str) -> usize {
             })
     });
 
-    antinodes.len()
+    antinodes
 }
 
-pub fn part2(input: &str) -> usize {
-    let (width, height, antennas) = parse_input(input);
+/// Locations in line with at least two antennas of the same frequency.
+fn resonant_antinodes((width, height, antennas): &Input) -> FxHashSet<(isize, isize)> {
+    let (width, height) = (*width, *height);
 
     let mut antinodes: FxHashSet<(isize, isize)> = FxHashSet::default();
 
@@ -108,24 +133,101 @@ pub fn part2(input: &str) -> usize {
             })
     });
 
-    antinodes.len()
+    antinodes
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The antennas coloured by frequency, with the antinodes of part 1 highlighted over the ones that
+/// only count for part 2.
+pub fn render(input: &Input) -> Picture {
+    let (width, height, antennas) = input;
 
-    const TEST_INPUT: &str = include_str!("../../examples/2024/day8.txt");
-    const TEST_INPUT_PART_2_EXAMPLE: &str = include_str!("../../examples/2024/day8_part_2.txt");
+    let mut pixels = HashMap::new();
+    for (index, frequency) in antennas.keys().sorted().enumerate() {
+        for position in &antennas[frequency] {
+            pixels.insert(*position, Pixel::new(*frequency, Color::palette(index)));
+        }
+    }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(14, part1(TEST_INPUT));
+    let mut picture = Picture::new(*width as usize, *height as usize, |x, y| {
+        let empty = Pixel::new('.', Color::DARK_GREY);
+        *pixels.get(&(x as isize, y as isize)).unwrap_or(&empty)
+    });
+
+    let index_of = |(x, y): (isize, isize)| y as usize * *width as usize + x as usize;
+    let resonant = Overlay {
+        background: Color::BLUE,
+        symbol: None,
+    };
+    let antinode = Overlay {
+        background: Color::GOLD,
+        symbol: None,
+    };
+
+    picture.highlight(
+        resonant_antinodes(input).into_iter().map(index_of),
+        resonant,
+    );
+    picture.highlight(antinodes(input).into_iter().map(index_of), antinode);
+
+    picture
+}
+
+/// A `width` by `height` map with an antenna on about one in 25 locations.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    generate::grid(size.width, size.height, |_, _| match rng.usize(..25) {
+        0 => rng.alphanumeric(),
+        _ => '.',
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+    const EXAMPLES: &'static [&'static str] =
+        &["examples/2024/day8.txt", "examples/2024/day8_part_2.txt"];
+
+    type Input = Input;
+    type Output = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
     }
 
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+
+    fn render(input: &Self::Input) -> Option<Picture> {
+        Some(render(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
-    fn test_part2() {
-        assert_eq!(9, part2(TEST_INPUT_PART_2_EXAMPLE));
-        assert_eq!(34, part2(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("..a.\n.#..\n").unwrap_err();
+        assert_eq!(
+            (2, 2, Some('#')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = parse_input("..a.\n.A...\n").unwrap_err();
+        assert_eq!(
+            (2, 5, Some('.')),
+            (error.line, error.column, error.character)
+        );
     }
 }
@@ -1,33 +1,32 @@
+use crate::generate::{Rng, Size};
+use crate::parse::{lines, ErrorKind};
+use crate::{ParseError, Solution, SolveError};
 use itertools::*;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::fmt::{Display, Formatter};
 
-fn parse_input(input: &str) -> Disk {
-    let mut disk = Disk::default();
-    let mut empty = false;
-
-    for count in input.lines().next().unwrap().chars() {
-        let length = count.to_digit(10).unwrap() as usize;
-
-        if empty {
-            empty = false;
-            disk.add_gap(length);
-        } else {
-            empty = true;
-            disk.add_block(length);
-        }
-    }
-
-    disk
+/// The disk map: alternating lengths of files and free space.
+pub fn parse_input(input: &str) -> Result<Vec<u8>, ParseError> {
+    let Some(line) = lines(9, input).next() else {
+        return Err(ParseError::missing(9, input, "the disk map"));
+    };
+
+    line.text
+        .char_indices()
+        .map(|(x, count)| match count.to_digit(10) {
+            Some(length) => Ok(length as u8),
+            None => Err(line.error(x, ErrorKind::UnexpectedCharacter)),
+        })
+        .collect()
 }
 
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &[u8]) -> u64 {
     let mut disk: Vec<Option<usize>> = Vec::new();
     let mut next_id = 0;
     let mut empty = false;
 
-    for count in input.lines().next().unwrap().chars() {
+    for count in input {
         let element = match empty {
             true => {
                 empty = false;
@@ -40,12 +39,17 @@ pub fn part1(input: &str) -> usize {
             }
         };
 
-        let count = count.to_digit(10).unwrap() as usize;
+        let count = *count as usize;
         disk.extend((0..count).map(|_| element));
     }
 
-    let mut first_empty_index = disk.iter().position(|element| element.is_none()).unwrap();
-    let mut last_used_index = disk.iter().rposition(|element| element.is_some()).unwrap();
+    // A disk without free space, or without files, is compact already
+    let (Some(mut first_empty_index), Some(mut last_used_index)) = (
+        disk.iter().position(|element| element.is_none()),
+        disk.iter().rposition(|element| element.is_some()),
+    ) else {
+        return checksum(&disk);
+    };
 
     while first_empty_index < last_used_index {
         disk.swap(first_empty_index, last_used_index);
@@ -62,38 +66,70 @@ pub fn part1(input: &str) -> usize {
         }
     }
 
+    checksum(&disk)
+}
+
+fn checksum(disk: &[Option<usize>]) -> u64 {
     disk.iter()
         .enumerate()
         .map(|(index, element)| match element {
             None => 0,
-            Some(element) => element * index,
+            Some(element) => *element as u64 * index as u64,
         })
         .sum()
 }
 
+/// Files and gaps of a disk map, for compacting whole files.
 #[derive(Default)]
-struct Disk {
+pub struct Disk {
     index: usize,
 
     blocks: Vec<Block>,
 
-    // Key: start index of the gap
-    // Value: length of the gap
-    gaps: [BinaryHeap<Reverse<usize>>; 10],
+    // Index: length of the gap
+    // Value: start indices of the gaps of that length
+    gaps: Vec<BinaryHeap<Reverse<usize>>>,
 }
 
 impl Disk {
-    fn checksum(&self) -> usize {
+    pub fn from_disk_map(input: &[u8]) -> Self {
+        let mut disk = Self::default();
+        let mut gap = 0;
+
+        for (index, length) in input.iter().enumerate() {
+            let length = *length as usize;
+
+            if index % 2 == 1 {
+                gap += length;
+            } else if length > 0 {
+                disk.add_gap(gap);
+                gap = 0;
+
+                // Files are numbered by their position in the map, empty ones included
+                disk.add_block(index / 2, length);
+            }
+        }
+
+        // The gaps around an empty file form one gap, which may be longer than any single digit
+        disk.add_gap(gap);
+        disk
+    }
+
+    pub fn checksum(&self) -> u64 {
         self.blocks.iter().map(|block| block.checksum()).sum()
     }
 
-    fn add_block(&mut self, length: usize) {
-        if length == 0 {
-            return;
+    /// Moves files into the leftmost gap they fit, one move per step.
+    pub fn compact(&mut self) -> Compaction<'_> {
+        Compaction {
+            block_index: self.blocks.len(),
+            disk: self,
         }
+    }
 
+    fn add_block(&mut self, id: usize, length: usize) {
         self.blocks.push(Block {
-            id: self.blocks.len(),
+            id,
             start_index: self.index,
             length,
         });
@@ -105,11 +141,19 @@ impl Disk {
             return;
         }
 
-        self.gaps[length].push(Reverse(self.index));
+        push_gap(&mut self.gaps, self.index, length);
         self.index += length;
     }
 }
 
+fn push_gap(gaps: &mut Vec<BinaryHeap<Reverse<usize>>>, index: usize, length: usize) {
+    if gaps.len() <= length {
+        gaps.resize_with(length + 1, BinaryHeap::new);
+    }
+
+    gaps[length].push(Reverse(index));
+}
+
 impl Display for Disk {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let elements = self
@@ -140,50 +184,135 @@ struct Block {
 }
 
 impl Block {
-    fn checksum(&self) -> usize {
+    fn checksum(&self) -> u64 {
         (0..self.length)
-            .map(|offset| (offset + self.start_index) * self.id)
+            .map(|offset| (offset + self.start_index) as u64 * self.id as u64)
             .sum()
     }
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut disk = parse_input(input);
+/// A file moved to the left by [`Disk::compact`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Move {
+    pub id: usize,
+    pub length: usize,
+    /// Index of the first block before and after the move.
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Iterator over the moves of [`Disk::compact`]. Every file is tried once, from the highest id
+/// down, and files that don't fit in a gap to their left are skipped.
+pub struct Compaction<'a> {
+    disk: &'a mut Disk,
+    block_index: usize,
+}
+
+impl Iterator for Compaction<'_> {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let disk = &mut *self.disk;
+
+        while self.block_index > 0 {
+            self.block_index -= 1;
 
-    for block_index in (0..disk.blocks.len()).rev() {
-        let block = &mut disk.blocks[block_index];
-        let mut earliest_large_enough_gap_index = block.start_index;
-        let mut earliest_large_enough_gap_size = None;
+            let block = &mut disk.blocks[self.block_index];
+            let mut earliest_large_enough_gap_index = block.start_index;
+            let mut earliest_large_enough_gap_size = None;
 
-        for gap_size in block.length..10 {
-            let Some(Reverse(gap_index)) = disk.gaps[gap_size].peek() else {
+            for gap_size in block.length..disk.gaps.len() {
+                let Some(Reverse(gap_index)) = disk.gaps[gap_size].peek() else {
+                    continue;
+                };
+
+                if *gap_index >= earliest_large_enough_gap_index {
+                    continue;
+                }
+
+                earliest_large_enough_gap_index = *gap_index;
+                earliest_large_enough_gap_size = Some(gap_size);
+            }
+
+            let Some(earliest_large_enough_gap_size) = earliest_large_enough_gap_size else {
                 continue;
             };
 
-            if *gap_index >= earliest_large_enough_gap_index {
-                continue;
+            disk.gaps[earliest_large_enough_gap_size].pop();
+            push_gap(&mut disk.gaps, block.start_index, block.length);
+
+            let from = block.start_index;
+            block.start_index = earliest_large_enough_gap_index;
+
+            if earliest_large_enough_gap_size > block.length {
+                push_gap(
+                    &mut disk.gaps,
+                    earliest_large_enough_gap_index + block.length,
+                    earliest_large_enough_gap_size - block.length,
+                );
             }
 
-            earliest_large_enough_gap_index = *gap_index;
-            earliest_large_enough_gap_size = Some(gap_size);
+            return Some(Move {
+                id: block.id,
+                length: block.length,
+                from,
+                to: block.start_index,
+            });
         }
 
-        let Some(earliest_large_enough_gap_size) = earliest_large_enough_gap_size else {
-            continue;
-        };
+        None
+    }
+}
 
-        disk.gaps[earliest_large_enough_gap_size].pop();
-        disk.gaps[block.length].push(Reverse(block.start_index));
+pub fn part2(input: &[u8]) -> u64 {
+    let mut disk = Disk::from_disk_map(input);
+    disk.compact().for_each(drop);
+    disk.checksum()
+}
 
-        block.start_index = earliest_large_enough_gap_index;
+/// A disk map of `count` digits, starting and ending with a non-empty file. The files in between
+/// may be empty.
+pub fn generate_input(rng: &mut Rng, size: &Size) -> String {
+    let length = size.count.max(3) | 1;
+
+    let mut input = (0..length)
+        .map(|index| match index {
+            // Part 1 expects at least some free space
+            1 => rng.char('1'..='9'),
+            _ if index == 0 || index == length - 1 => rng.char('1'..='9'),
+            _ => rng.char('0'..='9'),
+        })
+        .collect::<String>();
 
-        if earliest_large_enough_gap_size > block.length {
-            disk.gaps[earliest_large_enough_gap_size - block.length]
-                .push(Reverse(earliest_large_enough_gap_index + block.length));
-        }
+    input.push('\n');
+    input
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day9.txt"];
+
+    type Input = Vec<u8>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    disk.checksum()
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    fn generate(rng: &mut Rng, size: &Size) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
 #[cfg(test)]
@@ -193,12 +322,43 @@ mod tests {
     const TEST_INPUT: &str = include_str!("../../examples/2024/day9.txt");
 
     #[test]
-    fn test_part1() {
-        assert_eq!(1928, part1(TEST_INPUT));
+    fn test_compaction() {
+        let mut disk = Disk::from_disk_map(&parse_input(TEST_INPUT).unwrap());
+        let moves = disk.compact().collect::<Vec<_>>();
+
+        let first = Move {
+            id: 9,
+            length: 2,
+            from: 40,
+            to: 2,
+        };
+        assert_eq!(Some(&first), moves.first());
+        assert_eq!(4, moves.len());
+        assert_eq!(
+            "00992111777.44.333....5555.6666.....8888..",
+            disk.to_string()
+        );
+        assert_eq!(2858, disk.checksum());
+    }
+
+    /// An empty file still takes up an id, so the files after it keep theirs, and the gaps on
+    /// both sides of it are one gap.
+    #[test]
+    fn test_empty_file() {
+        let input = parse_input("10011\n").unwrap();
+        assert_eq!(2, part1(&input));
+        assert_eq!(2, part2(&input));
+
+        let input = parse_input("1101102\n").unwrap();
+        assert_eq!(3 + 3 * 2 + 2 * 3, part2(&input));
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(2858, part2(TEST_INPUT));
+    fn test_parse_error() {
+        let error = parse_input("2333133121414131402 \n").unwrap_err();
+        assert_eq!(
+            (1, 20, Some(' ')),
+            (error.line, error.column, error.character)
+        );
     }
 }
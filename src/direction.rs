@@ -0,0 +1,187 @@
+use strum::IntoEnumIterator;
+use strum_macros::{EnumCount, EnumIter};
+
+/// One of the four orthogonal directions, in clockwise order starting at the top.
+///
+/// `direction as usize` (or [`Direction::index`]) can be used to index arrays of length
+/// [`strum::EnumCount::COUNT`].
+#[derive(EnumIter, EnumCount, Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    /// Change in `(x, y)` when taking one step, with `y` growing downwards.
+    pub fn delta(self) -> (isize, isize) {
+        Compass::from(self).delta()
+    }
+}
+
+/// One of the eight compass directions including the diagonals, in clockwise order starting at
+/// the top.
+#[derive(EnumIter, EnumCount, Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Compass {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Compass {
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The four orthogonal directions.
+    pub fn orthogonal() -> impl Iterator<Item = Self> {
+        Direction::iter().map(Compass::from)
+    }
+
+    /// The four diagonal directions.
+    pub fn diagonal() -> impl Iterator<Item = Self> {
+        Compass::iter().filter(|direction| direction.is_diagonal())
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Rotates 45 degrees counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    /// Rotates 45 degrees clockwise.
+    pub fn rotate_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 6)
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// Change in `(x, y)` when taking one step, with `y` growing downwards.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Compass::Up => (0, -1),
+            Compass::UpRight => (1, -1),
+            Compass::Right => (1, 0),
+            Compass::DownRight => (1, 1),
+            Compass::Down => (0, 1),
+            Compass::DownLeft => (-1, 1),
+            Compass::Left => (-1, 0),
+            Compass::UpLeft => (-1, -1),
+        }
+    }
+
+    fn from_index(index: usize) -> Self {
+        match index % 8 {
+            0 => Compass::Up,
+            1 => Compass::UpRight,
+            2 => Compass::Right,
+            3 => Compass::DownRight,
+            4 => Compass::Down,
+            5 => Compass::DownLeft,
+            6 => Compass::Left,
+            _ => Compass::UpLeft,
+        }
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Compass::Up,
+            Direction::Right => Compass::Right,
+            Direction::Down => Compass::Down,
+            Direction::Left => Compass::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::EnumCount;
+
+    #[test]
+    fn test_direction() {
+        for direction in Direction::iter() {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.opposite(), direction.turn_right().turn_right());
+            assert_eq!(
+                Compass::from(direction).turn_right(),
+                Compass::from(direction.turn_right())
+            );
+        }
+
+        assert_eq!(4, Direction::COUNT);
+        assert_eq!((-1, 0), Direction::Left.delta());
+    }
+
+    #[test]
+    fn test_compass() {
+        assert_eq!(
+            vec![
+                Compass::UpRight,
+                Compass::DownRight,
+                Compass::DownLeft,
+                Compass::UpLeft
+            ],
+            Compass::diagonal().collect::<Vec<_>>()
+        );
+        assert_eq!(Compass::UpLeft, Compass::Up.rotate_left());
+        assert_eq!(Compass::DownLeft, Compass::UpRight.opposite());
+
+        for direction in Compass::iter() {
+            let (dx, dy) = direction.delta();
+            assert_eq!((-dx, -dy), direction.opposite().delta());
+        }
+    }
+}
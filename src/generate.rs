@@ -0,0 +1,86 @@
+pub use fastrand::Rng;
+
+/// How large a generated input should be. Every day only reads the dimensions that make sense for
+/// its puzzle and documents them on its `generate` function; the others are ignored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Size {
+    /// Columns of a grid, or the number of distinct items such as towel patterns.
+    pub width: usize,
+    /// Rows of a grid.
+    pub height: usize,
+    /// Number of lines or records, such as reports, equations or stones.
+    pub count: usize,
+}
+
+impl Size {
+    /// A square grid with `side` rows and columns, or `side * side` records.
+    pub fn square(side: usize) -> Self {
+        Self {
+            width: side,
+            height: side,
+            count: side * side,
+        }
+    }
+}
+
+/// Renders a grid of characters as puzzle input, one line per row.
+pub fn grid(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    let mut input = String::with_capacity((width + 1) * height);
+
+    for y in 0..height {
+        input.extend((0..width).map(|x| cell(x, y)));
+        input.push('\n');
+    }
+
+    input
+}
+
+/// Renders one line per item.
+pub fn lines<T>(items: impl IntoIterator<Item = T>, mut line: impl FnMut(T) -> String) -> String {
+    let mut input = String::new();
+
+    for item in items {
+        input.push_str(&line(item));
+        input.push('\n');
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DAYS;
+
+    /// Every day with a generator generates inputs its own parser accepts and can solve, and the
+    /// same seed always gives the same input.
+    #[test]
+    fn test_generate() {
+        let size = Size {
+            width: 12,
+            height: 9,
+            count: 30,
+        };
+
+        for solution in DAYS {
+            let Some(input) = solution.generate(2024, &size) else {
+                continue;
+            };
+            assert_eq!(Some(&input), solution.generate(2024, &size).as_ref());
+
+            let parsed = solution
+                .parse(&input)
+                .unwrap_or_else(|error| panic!("{}\n{}", error, input));
+            solution.part1(&*parsed).unwrap();
+
+            let part2 = solution.part2(&*parsed);
+            assert_eq!(
+                solution.has_part2(),
+                part2.is_some(),
+                "day {}",
+                solution.day()
+            );
+            part2.transpose().unwrap();
+        }
+    }
+}
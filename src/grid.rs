@@ -0,0 +1,404 @@
+use crate::direction::Compass;
+use crate::parse::{lines, ErrorKind};
+use crate::ParseError;
+use std::ops::{Index, IndexMut};
+use strum::{EnumCount, IntoEnumIterator};
+
+/// A rectangular grid stored row by row, addressed either by index or by `(x, y)` position with
+/// the origin in the top left corner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "Grid dimensions don't match");
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        debug_assert!(x < self.width && y < self.height);
+        y * self.width + x
+    }
+
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.width, index / self.width)
+    }
+
+    /// Index of the cell `dx` columns and `dy` rows away, `None` if that falls outside the grid.
+    #[inline]
+    pub fn neighbour(&self, index: usize, dx: isize, dy: isize) -> Option<usize> {
+        if dx != 0 {
+            let x = (index % self.width) as isize + dx;
+
+            if x < 0 || x >= self.width as isize {
+                return None;
+            }
+        }
+
+        // The column is known to be valid, so only the row can fall outside the grid
+        let target = index as isize + dy * self.width as isize + dx;
+
+        if target < 0 || target >= self.cells.len() as isize {
+            return None;
+        }
+
+        Some(target as usize)
+    }
+
+    /// Index of the adjacent cell in the given direction, `None` at the edge of the grid.
+    #[inline]
+    pub fn move_towards(&self, index: usize, direction: impl Into<Compass>) -> Option<usize> {
+        let (dx, dy) = direction.into().delta();
+        self.neighbour(index, dx, dy)
+    }
+
+    /// Every row from the top, also the empty rows of a grid without columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Indices of all cells matching the predicate, in reading order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(index, _)| index)
+    }
+
+    /// Index of the first cell equal to `marker`, e.g. the start of a maze.
+    pub fn find(&self, marker: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.cells.iter().position(|cell| cell == marker)
+    }
+}
+
+impl<T: TryFrom<char>> Grid<T> {
+    /// Parses a grid with one character per cell, skipping blank lines. Every line must be as wide
+    /// as the first one.
+    pub fn parse(day: u8, input: &str) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, str::len);
+        if width == 0 {
+            return Err(ParseError::missing(day, input, "the grid"));
+        }
+
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in lines(day, input).filter(|line| !line.text.is_empty()) {
+            for (x, c) in line.text.char_indices() {
+                if x == width {
+                    return Err(line.error(x, ErrorKind::UnexpectedCharacter));
+                }
+
+                match T::try_from(c) {
+                    Ok(cell) => cells.push(cell),
+                    Err(_) => return Err(line.error(x, ErrorKind::UnexpectedCharacter)),
+                }
+            }
+
+            if line.text.len() < width {
+                return Err(line.error(line.text.len(), ErrorKind::UnexpectedEndOfLine));
+            }
+
+            height += 1;
+        }
+
+        Ok(Self::new(width, height, cells))
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.cells[index]
+    }
+}
+
+/// Cell storage that can be walked one [`Compass`] step at a time. Implemented by both the plain
+/// [`Grid`] and the [`PaddedGrid`], so solvers can be written once for either layout.
+pub trait Layout<T>: Index<usize, Output = T> {
+    /// All stored cells, including any padding.
+    fn cells(&self) -> &[T];
+
+    /// Number of stored cells, including any padding.
+    fn size(&self) -> usize {
+        self.cells().len()
+    }
+
+    fn move_towards(&self, index: usize, direction: impl Into<Compass>) -> Option<usize>;
+}
+
+impl<T> Layout<T> for Grid<T> {
+    #[inline]
+    fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    #[inline]
+    fn move_towards(&self, index: usize, direction: impl Into<Compass>) -> Option<usize> {
+        Grid::move_towards(self, index, direction)
+    }
+}
+
+/// Cell value used for the border of a [`PaddedGrid`]. Walks have to stop when they reach it,
+/// e.g. because it is a wall or doesn't match anything they are looking for.
+pub trait Sentinel {
+    const SENTINEL: Self;
+}
+
+impl<T: Sentinel + Clone> Grid<T> {
+    pub fn padded(&self) -> PaddedGrid<T> {
+        PaddedGrid::from_grid(self)
+    }
+}
+
+/// A [`Grid`] surrounded by a one cell wide border of [`Sentinel`] cells. Moving one step from any
+/// cell inside the grid is a plain index addition, without checking for the edges of the grid.
+///
+/// Indices into a padded grid don't match the ones of the grid it was created from, use
+/// [`PaddedGrid::padded_index`] to convert between them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaddedGrid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    stride: usize,
+    offsets: [isize; Compass::COUNT],
+}
+
+impl<T: Sentinel + Clone> PaddedGrid<T> {
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        let stride = grid.width + 2;
+
+        let mut cells = vec![T::SENTINEL; stride * (grid.height + 2)];
+        for (y, row) in grid.rows().enumerate() {
+            let start = (y + 1) * stride + 1;
+            cells[start..start + grid.width].clone_from_slice(row);
+        }
+
+        let mut offsets = [0; Compass::COUNT];
+        for direction in Compass::iter() {
+            let (dx, dy) = direction.delta();
+            offsets[direction.index()] = dy * stride as isize + dx;
+        }
+
+        Self {
+            cells,
+            width: grid.width,
+            height: grid.height,
+            stride,
+            offsets,
+        }
+    }
+}
+
+impl<T> PaddedGrid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Distance between two vertically adjacent cells.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn index_of(&self, x: usize, y: usize) -> usize {
+        debug_assert!(x < self.width && y < self.height);
+        (y + 1) * self.stride + x + 1
+    }
+
+    pub fn position_of(&self, index: usize) -> (usize, usize) {
+        (index % self.stride - 1, index / self.stride - 1)
+    }
+
+    /// Converts an index into the unpadded grid to the matching index in this one.
+    pub fn padded_index(&self, index: usize) -> usize {
+        self.index_of(index % self.width, index / self.width)
+    }
+
+    /// Index offset of a single step in the given direction.
+    #[inline]
+    pub fn offset(&self, direction: impl Into<Compass>) -> isize {
+        self.offsets[direction.into().index()]
+    }
+}
+
+impl<T> Layout<T> for PaddedGrid<T> {
+    #[inline]
+    fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Never fails for cells inside the grid, stepping from the border itself is not supported.
+    #[inline]
+    fn move_towards(&self, index: usize, direction: impl Into<Compass>) -> Option<usize> {
+        Some(index.wrapping_add_signed(self.offset(direction)))
+    }
+}
+
+impl<T> Index<usize> for PaddedGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.cells[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    enum Cell {
+        Open,
+        Wall,
+        Start,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = ();
+
+        fn try_from(value: char) -> Result<Self, Self::Error> {
+            match value {
+                '.' => Ok(Cell::Open),
+                '#' => Ok(Cell::Wall),
+                'S' => Ok(Cell::Start),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl Sentinel for Cell {
+        const SENTINEL: Self = Cell::Wall;
+    }
+
+    const TEST_INPUT: &str = "#..\n.S#\n";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::<Cell>::parse(0, TEST_INPUT).unwrap();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(4), grid.find(&Cell::Start));
+        assert_eq!((1, 1), grid.position_of(4));
+        assert_eq!(4, grid.index_of(1, 1));
+        assert_eq!(
+            vec![0, 5],
+            grid.positions(|cell| *cell == Cell::Wall)
+                .collect::<Vec<_>>()
+        );
+
+        let error = Grid::<Cell>::parse(0, "#..\n.x#\n").unwrap_err();
+        assert_eq!(
+            (2, 2, Some('x')),
+            (error.line, error.column, error.character)
+        );
+
+        let error = Grid::<Cell>::parse(0, "#..\n.#\n").unwrap_err();
+        assert_eq!((2, 3, None), (error.line, error.column, error.character));
+    }
+
+    #[test]
+    fn test_neighbour() {
+        let grid = Grid::<Cell>::parse(0, TEST_INPUT).unwrap();
+
+        assert_eq!(Some(1), grid.neighbour(4, 0, -1));
+        assert_eq!(None, grid.neighbour(4, 0, 1));
+        assert_eq!(Some(3), grid.neighbour(4, -1, 0));
+        assert_eq!(None, grid.neighbour(3, -1, 0));
+        assert_eq!(None, grid.neighbour(2, 1, 1));
+        assert_eq!(Some(2), grid.neighbour(4, 1, -1));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::<Cell>::parse(0, TEST_INPUT).unwrap();
+
+        assert_eq!(
+            vec![Cell::Wall, Cell::Open, Cell::Open],
+            grid.rows().next().unwrap()
+        );
+        assert_eq!(
+            vec![Cell::Open, Cell::Wall],
+            grid.column(2).copied().collect::<Vec<_>>()
+        );
+        assert_eq!(3, grid.columns().count());
+    }
+
+    #[test]
+    fn test_padded() {
+        let grid = Grid::<Cell>::parse(0, TEST_INPUT).unwrap();
+        let padded = grid.padded();
+
+        assert_eq!(5, padded.stride());
+        assert_eq!(20, padded.cells().len());
+        assert_eq!(12, padded.padded_index(4));
+        assert_eq!((1, 1), padded.position_of(12));
+        assert_eq!(Cell::Start, padded[12]);
+
+        for direction in Compass::iter() {
+            let Some(next) = grid.move_towards(4, direction) else {
+                let next = Layout::move_towards(&padded, 12, direction).unwrap();
+                assert_eq!(Cell::SENTINEL, padded[next]);
+                continue;
+            };
+
+            let padded_next = Layout::move_towards(&padded, 12, direction);
+            assert_eq!(Some(padded.padded_index(next)), padded_next);
+            assert_eq!(grid[next], padded[padded_next.unwrap()]);
+        }
+    }
+}
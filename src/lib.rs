@@ -1,7 +1,22 @@
-extern crate aoc_runner;
+pub mod answer;
+pub mod answers;
+pub mod direction;
+pub mod generate;
+pub mod grid;
+pub mod parse;
+#[cfg(test)]
+mod reference;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod stats;
+pub mod timing;
 
-#[macro_use]
-extern crate aoc_runner_derive;
+pub use answer::Answer;
+pub use parse::ParseError;
+pub use solution::{DynSolution, Part, Solution, SolveError};
 
 pub mod day1;
 pub mod day10;
@@ -17,4 +32,67 @@ pub mod day7;
 pub mod day8;
 pub mod day9;
 
-aoc_lib! { year = 2024 }
+/// Every implemented day in calendar order. A new day needs its module declared above and its
+/// solution listed here.
+pub const DAYS: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day16::Day16,
+    &day19::Day19,
+    &day25::Day25,
+];
+
+/// Looks up the solution of the given day in [`DAYS`].
+pub fn find_day(day: u8) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().copied().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].day() < pair[1].day()));
+    }
+
+    /// Every day gives the same answers on its examples after an editor on Windows had them.
+    #[test]
+    fn test_normalized_examples() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        for solution in DAYS {
+            for example in solution.examples() {
+                let input = std::fs::read_to_string(root.join(example)).unwrap();
+                let variants = [
+                    input.replace('\n', "\r\n"),
+                    format!("\u{feff}{}", input),
+                    format!("\u{feff}{}", input.trim_end().replace('\n', " \r\n")),
+                ];
+
+                for part in [Part::One, Part::Two] {
+                    let expected = solution.solve(&input, part).unwrap();
+
+                    for variant in &variants {
+                        assert_eq!(
+                            expected,
+                            solution.solve(variant, part).unwrap(),
+                            "{} {} on {:?}",
+                            example,
+                            part,
+                            variant
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
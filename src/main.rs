@@ -1,7 +1,632 @@
-extern crate advent_of_code_2024;
-extern crate aoc_runner;
-extern crate aoc_runner_derive;
+use advent_of_code_2024::answers::{read_answers, verify, Verdict};
+use advent_of_code_2024::runner::{
+    capture_panics, render, run, run_parallel, run_with_budget, to_json, DayRun, Input, Source,
+    Status,
+};
+use advent_of_code_2024::scaffold::scaffold;
+use advent_of_code_2024::timing::{self, delta, measure, Baseline};
+use advent_of_code_2024::{find_day, DynSolution, Part, DAYS};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-use aoc_runner_derive::aoc_main;
+fn command() -> Command {
+    Command::new("advent-of-code-2024")
+        .about("Solves the Advent of Code 2024 puzzles")
+        .args_conflicts_with_subcommands(true)
+        .subcommand(
+            Command::new("new")
+                .about("Creates and registers the module of a day that isn't implemented yet")
+                .arg(
+                    Arg::new("day")
+                        .value_name("DAY")
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("title")
+                        .short('t')
+                        .long("title")
+                        .value_name("TITLE")
+                        .default_value("")
+                        .help("Title of the puzzle as shown on the website"),
+                ),
+        )
+        .arg(
+            Arg::new("day")
+                .value_name("DAY")
+                .value_parser(value_parser!(u8).range(1..=25))
+                .help("Day to solve, every implemented day if absent"),
+        )
+        .arg(
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .value_name("PART")
+                .value_parser(value_parser!(u8).range(1..=2))
+                .help("Part to solve, both if absent"),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .requires("day")
+                .help("Read the input from this file, or from stdin for '-'"),
+        )
+        .arg(
+            Arg::new("example")
+                .short('e')
+                .long("example")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("input")
+                .help("Solve the examples of the puzzle instead of the real input"),
+        )
+        .arg(
+            Arg::new("budget")
+                .long("budget")
+                .value_name("MS")
+                .value_parser(value_parser!(u64))
+                .help("Give up on parsing or solving a part after this many milliseconds"),
+        )
+        .arg(
+            Arg::new("parallel")
+                .long("parallel")
+                .action(ArgAction::SetTrue)
+                .help("Solve every day and part at the same time and print a summary table"),
+        )
+        .arg(
+            Arg::new("repeat")
+                .long("repeat")
+                .value_name("N")
+                .value_parser(value_parser!(u64).range(1..))
+                .conflicts_with_all(["budget", "parallel", "json"])
+                .help("Time parsing and solving over N runs and print their distribution"),
+        )
+        .arg(
+            Arg::new("warmup")
+                .long("warmup")
+                .value_name("N")
+                .value_parser(value_parser!(u64))
+                .default_value("1")
+                .requires("repeat")
+                .help("Unmeasured runs before the timed ones"),
+        )
+        .arg(
+            Arg::new("save")
+                .long("save")
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .requires("repeat")
+                .help("Write the timings to this file"),
+        )
+        .arg(
+            Arg::new("compare")
+                .long("compare")
+                .value_name("PATH")
+                .value_parser(value_parser!(PathBuf))
+                .requires("repeat")
+                .help("Show the change of every median against timings saved earlier"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("Print the results as a JSON document instead of text"),
+        )
+        .arg(
+            Arg::new("render")
+                .long("render")
+                .value_name("FORMAT")
+                .value_parser(["ansi", "ppm"])
+                .requires("day")
+                .conflicts_with_all(["repeat", "parallel", "json"])
+                .help(
+                    "Draw every input with the solution over it, to the terminal or as PPM images",
+                ),
+        )
+        .arg(
+            Arg::new("verify")
+                .long("verify")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["day", "part", "input", "example", "repeat"])
+                .help("Compare the answers for every input against inputs/answers.txt"),
+        )
+}
 
-aoc_main! { lib = advent_of_code_2024 }
+/// Compares the answers for every input in `inputs` against `inputs/answers.txt`.
+fn verify_all() -> ExitCode {
+    let answers = match read_answers(Path::new(".")) {
+        Ok(answers) => answers,
+        Err(error) => {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (mut correct, mut failed, mut skipped) = (0, 0, 0);
+
+    for solution in DAYS {
+        for (part, verdict) in verify(*solution, Path::new("."), &answers) {
+            println!("Day {} - {}: {}", solution.day(), part, verdict);
+
+            if verdict.is_failure() {
+                failed += 1;
+            } else if verdict == Verdict::Correct {
+                correct += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} correct, {} failed, {} skipped",
+        correct, failed, skipped
+    );
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Human readable outcome of anything but a successful run.
+fn describe(status: Status, message: Option<&str>) -> String {
+    let label = match status {
+        Status::Ok => "ok",
+        Status::Skipped => "skipped",
+        Status::Error => "error",
+        Status::Panicked => "panicked",
+        Status::TimedOut => "timed out",
+    };
+
+    match (status, message) {
+        // Parse errors already describe themselves
+        (Status::Error, Some(message)) => message.to_string(),
+        (_, Some(message)) => format!("{}, {}", label, message),
+        (_, None) => label.to_string(),
+    }
+}
+
+fn print_run(run: &DayRun, source: &Source) {
+    if *source != Source::Inputs {
+        if let Some(input) = &run.input {
+            println!("Day {} - {} ({})", run.day, run.title, input);
+        }
+    }
+
+    if run.status != Status::Ok {
+        let description = describe(run.status, run.message.as_deref());
+        return println!("Day {} - {}: {}\n", run.day, run.title, description);
+    }
+
+    for part in &run.parts {
+        let counters = part
+            .counters
+            .iter()
+            .map(|(name, count)| format!(",\n\t{}: {}", name, count))
+            .collect::<String>();
+
+        match (part.status, &part.answer) {
+            (Status::Ok, Some(answer)) => println!(
+                "Day {} - {}: {}\n\tparse: {:?},\n\tsolve: {:?}{}\n",
+                run.day,
+                part.part,
+                answer,
+                run.parse_time.unwrap_or_default(),
+                part.solve_time.unwrap_or_default(),
+                counters
+            ),
+            _ => println!(
+                "Day {} - {}: {}\n",
+                run.day,
+                part.part,
+                describe(part.status, part.message.as_deref())
+            ),
+        }
+    }
+}
+
+/// Summary of a parallel run, with a single row per day and input.
+fn print_table(runs: &[DayRun]) {
+    println!(
+        "{:>3}  {:<24}{:<18}{:<18}{:>10}{:>10}{:>10}",
+        "Day", "Title", "Part 1", "Part 2", "Parse", "Part 1", "Part 2"
+    );
+
+    for run in runs {
+        let mut answers = [String::new(), String::new()];
+        let mut times = [String::new(), String::new()];
+
+        if run.status != Status::Ok {
+            answers[0] = describe(run.status, None);
+        }
+
+        for part in &run.parts {
+            let index = part.part.number() as usize - 1;
+            answers[index] = match (&part.answer, part.status) {
+                (Some(answer), _) => answer.to_string(),
+                (None, status) => describe(status, None),
+            };
+            times[index] = part
+                .solve_time
+                .map_or_else(String::new, |time| format!("{:.1?}", time));
+        }
+
+        let parse_time = run
+            .parse_time
+            .map_or_else(String::new, |time| format!("{:.1?}", time));
+
+        println!(
+            "{:>3}  {:<24}{:<18}{:<18}{:>10}{:>10}{:>10}",
+            run.day, run.title, answers[0], answers[1], parse_time, times[0], times[1]
+        );
+    }
+}
+
+fn is_failure(status: Status) -> bool {
+    matches!(status, Status::Error | Status::Panicked | Status::TimedOut)
+}
+
+/// Describes every day and part that didn't run successfully.
+fn failures(runs: &[DayRun], skip_missing: bool) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    for run in runs {
+        if is_failure(run.status) || (run.status == Status::Skipped && !skip_missing) {
+            let description = describe(run.status, run.message.as_deref());
+            failures.push(format!("Day {} - {}: {}", run.day, run.title, description));
+        }
+
+        for part in run.parts.iter().filter(|part| is_failure(part.status)) {
+            let description = describe(part.status, part.message.as_deref());
+            failures.push(format!("Day {} - {}: {}", run.day, part.part, description));
+        }
+    }
+
+    failures
+}
+
+/// Times every input and prints the distribution of each phase, compared to the saved medians if
+/// there are any. Returns the days that could not be timed.
+fn time_all(
+    inputs: &[(&'static dyn DynSolution, Input)],
+    parts: &[Part],
+    matches: &ArgMatches,
+) -> Result<Vec<String>, String> {
+    let baseline = match matches.get_one::<PathBuf>("compare") {
+        Some(path) => match std::fs::read_to_string(path).map(|json| Baseline::parse(&json)) {
+            Ok(Ok(baseline)) => baseline,
+            Ok(Err(error)) => {
+                return Err(format!("Invalid timings in {}: {}", path.display(), error))
+            }
+            Err(error) => return Err(format!("Cannot read {}: {}", path.display(), error)),
+        },
+        None => Baseline::default(),
+    };
+
+    let repeat = *matches
+        .get_one::<u64>("repeat")
+        .expect("timing requires --repeat") as usize;
+    let warmup = *matches
+        .get_one::<u64>("warmup")
+        .expect("--warmup has a default") as usize;
+
+    println!(
+        "{:>3}  {:<24}{:<8}{:>10}{:>10}{:>10}{:>10}{:>10}",
+        "Day", "Title", "Phase", "Min", "Median", "P95", "Max", "Change"
+    );
+
+    let mut timings = Vec::new();
+    let mut failures = Vec::new();
+
+    for (solution, input) in inputs {
+        let timing = match measure(*solution, input, parts, warmup, repeat) {
+            Ok(timing) => timing,
+            Err((status, message)) => {
+                let description = describe(status, Some(&message));
+                failures.push(format!(
+                    "Day {} - {}: {}",
+                    solution.day(),
+                    solution.title(),
+                    description
+                ));
+                continue;
+            }
+        };
+
+        for (phase, stats) in &timing.phases {
+            let change = baseline
+                .median(&timing, *phase)
+                .map_or_else(String::new, |before| {
+                    format!("{:+.1}%", delta(before, stats.median))
+                });
+
+            println!(
+                "{:>3}  {:<24}{:<8}{:>10}{:>10}{:>10}{:>10}{:>10}",
+                timing.day,
+                timing.title,
+                phase.to_string(),
+                format!("{:.1?}", stats.min),
+                format!("{:.1?}", stats.median),
+                format!("{:.1?}", stats.p95),
+                format!("{:.1?}", stats.max),
+                change
+            );
+        }
+
+        timings.push(timing);
+    }
+
+    if let Some(path) = matches.get_one::<PathBuf>("save") {
+        std::fs::write(path, timing::to_json(&timings))
+            .map_err(|error| format!("Cannot write {}: {}", path.display(), error))?;
+    }
+
+    Ok(failures)
+}
+
+/// Side of the square of pixels that every cell of a grid becomes in a PPM image.
+const PPM_SCALE: usize = 8;
+
+/// Draws every input to the terminal, or to a PPM image in the working directory named after the
+/// input. Returns the inputs that could not be drawn; parse errors are reported by the run already.
+fn render_all(inputs: &[(&'static dyn DynSolution, Input)], format: &str) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    for (solution, input) in inputs {
+        let failure = |description: String| {
+            format!(
+                "Day {} - {}: {}",
+                solution.day(),
+                solution.title(),
+                description
+            )
+        };
+
+        let picture = match render(*solution, input) {
+            Ok(Some(picture)) => picture,
+            Ok(None) => {
+                failures.push(failure("cannot render".to_string()));
+                continue;
+            }
+            Err((Status::Error, _)) => continue,
+            Err((status, message)) => {
+                failures.push(failure(describe(status, Some(&message))));
+                continue;
+            }
+        };
+
+        if format == "ansi" {
+            println!("{}:\n{}", input.label, picture.to_ansi());
+            continue;
+        }
+
+        let name = match Path::new(&input.label).file_stem() {
+            Some(stem) if input.label != "-" => stem.to_string_lossy().into_owned(),
+            _ => format!("day{}", solution.day()),
+        };
+        let path = PathBuf::from(format!("{}.ppm", name));
+
+        match std::fs::write(&path, picture.to_ppm(PPM_SCALE)) {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(error) => failures.push(failure(format!(
+                "cannot write {}: {}",
+                path.display(),
+                error
+            ))),
+        }
+    }
+
+    failures
+}
+
+/// Lists the failures, if any, and turns them into the exit code.
+fn report(failures: &[String]) -> ExitCode {
+    if failures.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+
+    println!("Failures:");
+    for failure in failures {
+        println!("\t{}", failure);
+    }
+
+    ExitCode::FAILURE
+}
+
+/// Scaffolds a new day in the crate in the working directory.
+fn new_day(matches: &ArgMatches) -> ExitCode {
+    let day = *matches.get_one::<u8>("day").expect("the day is required");
+    let title = matches
+        .get_one::<String>("title")
+        .expect("the title has a default");
+
+    match scaffold(Path::new("."), day, title) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+            println!(
+                "Add the answers to the example to examples/2024/manifest.txt, the example test fails until then"
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Cannot add day {}: {}", day, error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn source(matches: &ArgMatches) -> Source {
+    if matches.get_flag("example") {
+        return Source::Examples;
+    }
+
+    match matches.get_one::<PathBuf>("input") {
+        Some(path) if path.as_os_str() == "-" => Source::Stdin,
+        Some(path) => Source::Path(path.clone()),
+        None => Source::Inputs,
+    }
+}
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+
+    if let Some(("new", matches)) = matches.subcommand() {
+        return new_day(matches);
+    }
+
+    if matches.get_flag("verify") {
+        return verify_all();
+    }
+
+    let solutions: Vec<&'static dyn DynSolution> = match matches.get_one::<u8>("day") {
+        Some(day) => match find_day(*day) {
+            Some(solution) => vec![solution],
+            None => {
+                eprintln!("Day {} is not implemented", day);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => DAYS.to_vec(),
+    };
+
+    let parts = match matches.get_one::<u8>("part") {
+        Some(1) => vec![Part::One],
+        Some(_) => vec![Part::Two],
+        None => vec![Part::One, Part::Two],
+    };
+
+    let source = source(&matches);
+    let run_all = solutions.len() > 1;
+    let json = matches.get_flag("json");
+    let budget = matches
+        .get_one::<u64>("budget")
+        .map(|ms| Duration::from_millis(*ms));
+    let mut runs = Vec::new();
+    let mut inputs = Vec::new();
+
+    capture_panics();
+
+    for solution in solutions {
+        match source.read(solution) {
+            Ok(day_inputs) => inputs.extend(day_inputs.into_iter().map(|input| (solution, input))),
+            Err(reason) => runs.push(DayRun::skipped(solution.day(), solution.title(), reason)),
+        }
+    }
+
+    // Running every day on the real inputs skips the ones that haven't been downloaded
+    let skip_missing = run_all && source == Source::Inputs;
+
+    if matches.contains_id("repeat") {
+        let mut failures = failures(&runs, skip_missing);
+
+        match time_all(&inputs, &parts, &matches) {
+            Ok(timing_failures) => failures.extend(timing_failures),
+            Err(error) => {
+                eprintln!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+
+        return report(&failures);
+    }
+
+    let parallel = matches.get_flag("parallel");
+    let start = Instant::now();
+    let mut cpu_time = Duration::ZERO;
+
+    if parallel {
+        let (parallel_runs, time) = run_parallel(&inputs, &parts, budget);
+        runs.extend(parallel_runs);
+        cpu_time = time;
+    } else {
+        for (solution, input) in &inputs {
+            runs.push(match budget {
+                Some(budget) => run_with_budget(*solution, input, &parts, budget),
+                None => run(*solution, input, &parts),
+            });
+        }
+    }
+
+    let wall_time = start.elapsed();
+    runs.sort_by_key(|run| run.day);
+
+    let mut failures = failures(&runs, skip_missing);
+
+    if json {
+        if run_all {
+            // Cover the whole calendar, so that runs can be compared as more days get solved
+            for day in 1..=25 {
+                if find_day(day).is_none() {
+                    runs.push(DayRun::skipped(day, "", "not implemented".to_string()));
+                }
+            }
+            runs.sort_by_key(|run| run.day);
+        }
+
+        println!("{}", to_json(&runs, &parts));
+    } else if parallel {
+        print_table(&runs);
+        println!(
+            "\nCPU time: {:.2?}, wall-clock time: {:.2?}",
+            cpu_time, wall_time
+        );
+    } else {
+        println!("Advent of code 2024");
+
+        for run in &runs {
+            print_run(run, &source);
+        }
+
+        if let Some(format) = matches.get_one::<String>("render") {
+            failures.extend(render_all(&inputs, format));
+        }
+    }
+
+    if json {
+        return if failures.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    report(&failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command() {
+        command().debug_assert();
+
+        let matches = command().get_matches_from(["aoc", "16", "-p", "2", "-i", "-"]);
+        assert_eq!(Source::Stdin, source(&matches));
+        assert!(command().try_get_matches_from(["aoc", "26"]).is_err());
+        assert!(command().try_get_matches_from(["aoc", "new", "6"]).is_ok());
+        assert!(command()
+            .try_get_matches_from(["aoc", "--warmup", "3"])
+            .is_err());
+        assert!(command()
+            .try_get_matches_from(["aoc", "--repeat", "0"])
+            .is_err());
+        assert!(command()
+            .try_get_matches_from(["aoc", "16", "--render", "ppm"])
+            .is_ok());
+        assert!(command()
+            .try_get_matches_from(["aoc", "--render", "ansi"])
+            .is_err());
+        assert!(command()
+            .try_get_matches_from(["aoc", "16", "--render", "svg"])
+            .is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Why a puzzle input was rejected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The character has no meaning at this position.
+    UnexpectedCharacter,
+    /// The line ended while more was expected.
+    UnexpectedEndOfLine,
+    /// Something specific was expected at this position.
+    Expected(&'static str),
+    /// A literal separator was expected at this position.
+    ExpectedDelimiter(&'static str),
+    /// A required part of the input is absent altogether.
+    Missing(&'static str),
+    /// A number does not fit in the range the solver supports.
+    NumberTooLarge,
+    /// The input is larger than the solver supports.
+    TooLarge,
+}
+
+/// Error returned by every parser, pointing at the offending location of the input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// One based line number.
+    pub line: usize,
+    /// One based column number, in characters.
+    pub column: usize,
+    /// The character at the reported position, `None` at the end of a line or of the input.
+    pub character: Option<char>,
+    pub kind: ErrorKind,
+}
+
+impl ParseError {
+    /// Creates an error from the zero based indices produced by `enumerate`.
+    pub fn new(
+        day: u8,
+        line_index: usize,
+        column_index: usize,
+        character: Option<char>,
+        kind: ErrorKind,
+    ) -> Self {
+        Self {
+            day,
+            line: line_index + 1,
+            column: column_index + 1,
+            character,
+            kind,
+        }
+    }
+
+    /// An error for something that is absent from the input, reported just after its last line.
+    pub fn missing(day: u8, input: &str, what: &'static str) -> Self {
+        Self::new(
+            day,
+            input.lines().count(),
+            0,
+            None,
+            ErrorKind::Missing(what),
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: ",
+            self.day, self.line, self.column
+        )?;
+
+        let found = match self.character {
+            Some(character) => format!("{:?}", character),
+            None => "end of line".to_string(),
+        };
+
+        match self.kind {
+            ErrorKind::UnexpectedCharacter => write!(f, "unexpected character {}", found),
+            ErrorKind::UnexpectedEndOfLine => write!(f, "unexpected end of line"),
+            ErrorKind::Expected(what) => write!(f, "expected {}, found {}", what, found),
+            ErrorKind::ExpectedDelimiter(what) => {
+                write!(f, "expected {:?}, found {}", what, found)
+            }
+            ErrorKind::Missing(what) => write!(f, "missing {}", what),
+            ErrorKind::NumberTooLarge => write!(f, "number starting at {} is too large", found),
+            ErrorKind::TooLarge => write!(f, "input is too large, exceeded at {}", found),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of puzzle input that knows its position, so that errors can point into it.
+#[derive(Copy, Clone, Debug)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub index: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Reports an error at the given byte offset of this line.
+    pub fn error(&self, offset: usize, kind: ErrorKind) -> ParseError {
+        let column = self.text[..offset].chars().count();
+        let character = self.text[offset..].chars().next();
+
+        ParseError::new(self.day, self.index, column, character, kind)
+    }
+
+    /// Reports an error at the start of `part`, which must be a slice of this line.
+    pub fn error_at(&self, part: &str, kind: ErrorKind) -> ParseError {
+        self.error(self.offset_of(part), kind)
+    }
+
+    /// Parses `part`, a slice of this line, as a decimal number.
+    pub fn number<T: FromStr>(&self, part: &str) -> Result<T, ParseError> {
+        if part.is_empty() {
+            return Err(self.error_at(part, ErrorKind::Expected("a number")));
+        }
+
+        if let Some(offset) = part.find(|c: char| !c.is_ascii_digit()) {
+            let kind = match offset {
+                0 => ErrorKind::Expected("a number"),
+                _ => ErrorKind::UnexpectedCharacter,
+            };
+
+            return Err(self.error(self.offset_of(part) + offset, kind));
+        }
+
+        part.parse()
+            .map_err(|_| self.error_at(part, ErrorKind::NumberTooLarge))
+    }
+
+    /// Splits the line around the first `delimiter`. When it is absent the error points at the
+    /// first character that is not part of the leading number, which is where the delimiter was
+    /// expected.
+    pub fn split_once(&self, delimiter: &'static str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text.split_once(delimiter).ok_or_else(|| {
+            let offset = self
+                .text
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(self.text.len());
+
+            self.error(offset, ErrorKind::ExpectedDelimiter(delimiter))
+        })
+    }
+
+    /// Byte offset of `part`, which must be a slice of this line.
+    pub fn offset_of(&self, part: &str) -> usize {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(offset <= self.text.len(), "part is not a slice of the line");
+
+        offset
+    }
+}
+
+/// Undoes what editors tend to do to a saved puzzle input: a leading byte order mark, `\r\n` line
+/// endings, whitespace at the end of lines and a missing final newline. Inputs that are already
+/// in the expected shape are borrowed as is.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let clean = !input.contains('\r')
+        && (input.is_empty() || input.ends_with('\n'))
+        && input
+            .lines()
+            .all(|line| line.len() == line.trim_end().len());
+
+    if clean {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+
+    Cow::Owned(normalized)
+}
+
+/// Iterates over the lines of `input` for the given day.
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(index, text)| Line { day, index, text })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line {
+            day: 1,
+            index: 2,
+            text,
+        }
+    }
+
+    #[test]
+    fn test_number() {
+        let text = "42";
+        assert_eq!(Ok(42), line(text).number::<u32>(text));
+
+        let text = "12 3x4";
+        let error = line(text).number::<u32>(&text[3..]).unwrap_err();
+        assert_eq!(
+            (3, 5, Some('x')),
+            (error.line, error.column, error.character)
+        );
+        assert_eq!(ErrorKind::UnexpectedCharacter, error.kind);
+
+        let text = "300";
+        let error = line(text).number::<u8>(text).unwrap_err();
+        assert_eq!(ErrorKind::NumberTooLarge, error.kind);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("1 2\n3 4\n"), Cow::Borrowed(_)));
+        assert_eq!("1 2\n\n3 4\n", normalize("\u{feff}1 2 \r\n\r\n3 4\t"));
+        assert_eq!("", normalize("\u{feff}"));
+    }
+
+    #[test]
+    fn test_split_once() {
+        let error = line("3 4").split_once("   ").unwrap_err();
+        assert_eq!((2, Some(' ')), (error.column, error.character));
+        assert_eq!(
+            "day 1, line 3, column 2: expected \"   \", found ' '",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,544 @@
+//! Straightforward reference solutions for the days whose solvers rely on shortcuts, used to
+//! check the solvers on many generated inputs. They parse the input themselves and favour being
+//! obviously correct over being fast.
+
+fn numbers(line: &str) -> Vec<usize> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .filter(|number| !number.is_empty())
+        .map(|number| number.parse().unwrap())
+        .collect()
+}
+
+fn grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+pub mod day2 {
+    use super::numbers;
+
+    fn is_safe(report: &[usize]) -> bool {
+        let steps_up = report
+            .windows(2)
+            .all(|pair| (1..=3).contains(&(pair[1] as i64 - pair[0] as i64)));
+        let steps_down = report
+            .windows(2)
+            .all(|pair| (1..=3).contains(&(pair[0] as i64 - pair[1] as i64)));
+
+        steps_up || steps_down
+    }
+
+    /// Safe reports, and safe reports after removing any single level.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let reports = input.lines().map(numbers).collect::<Vec<_>>();
+
+        let part1 = reports.iter().filter(|report| is_safe(report)).count();
+        let part2 = reports
+            .iter()
+            .filter(|report| {
+                (0..report.len()).any(|removed| {
+                    let mut report = report.to_vec();
+                    report.remove(removed);
+                    is_safe(&report)
+                })
+            })
+            .count();
+
+        (part1, part2)
+    }
+}
+
+pub mod day5 {
+    use super::numbers;
+    use std::collections::HashSet;
+
+    /// Middle pages of the correct updates, and of the incorrect ones once sorted by the rules.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+        let rules = rules
+            .lines()
+            .map(|rule| (numbers(rule)[0], numbers(rule)[1]))
+            .collect::<HashSet<_>>();
+
+        let (mut part1, mut part2) = (0, 0);
+
+        for update in updates.lines().map(numbers) {
+            let correct = (0..update.len())
+                .all(|i| (i + 1..update.len()).all(|j| !rules.contains(&(update[j], update[i]))));
+
+            if correct {
+                part1 += update[update.len() / 2];
+                continue;
+            }
+
+            // Repeatedly move a page that must come before all the others to the front
+            let mut remaining = update.clone();
+            let mut sorted = Vec::new();
+
+            while !remaining.is_empty() {
+                let first = (0..remaining.len())
+                    .find(|&i| {
+                        remaining
+                            .iter()
+                            .all(|&other| !rules.contains(&(other, remaining[i])))
+                    })
+                    .unwrap();
+                sorted.push(remaining.remove(first));
+            }
+
+            part2 += sorted[sorted.len() / 2];
+        }
+
+        (part1, part2)
+    }
+}
+
+pub mod day7 {
+    use super::numbers;
+
+    /// Whether any combination of operators makes the equation true, trying all of them.
+    fn solvable(test: usize, numbers: &[usize], operators: u32) -> bool {
+        let combinations = operators.pow(numbers.len() as u32 - 1);
+
+        (0..combinations).any(|mut combination| {
+            let mut total = numbers[0] as u128;
+
+            for number in &numbers[1..] {
+                let number = *number as u128;
+                total = match combination % operators {
+                    0 => total + number,
+                    1 => total * number,
+                    _ => format!("{}{}", total, number).parse().unwrap(),
+                };
+                combination /= operators;
+            }
+
+            total == test as u128
+        })
+    }
+
+    pub fn solve(input: &str) -> (usize, usize) {
+        let equations = input.lines().map(numbers).collect::<Vec<_>>();
+
+        let total = |operators| {
+            equations
+                .iter()
+                .filter(|numbers| solvable(numbers[0], &numbers[1..], operators))
+                .map(|numbers| numbers[0])
+                .sum()
+        };
+
+        (total(2), total(3))
+    }
+}
+
+pub mod day8 {
+    use super::grid;
+    use std::collections::HashMap;
+
+    /// Checks every location against every pair of antennas of the same frequency. Antinodes lie
+    /// a whole number of antenna spacings away from the antennas.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let grid = grid(input);
+        let mut antennas: HashMap<char, Vec<(i64, i64)>> = HashMap::new();
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell != '.' {
+                    antennas
+                        .entry(*cell)
+                        .or_default()
+                        .push((x as i64, y as i64));
+                }
+            }
+        }
+
+        let pairs = antennas
+            .values()
+            .flat_map(|group| {
+                group
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(i, a)| group[i + 1..].iter().map(move |b| (*a, *b)))
+            })
+            .collect::<Vec<_>>();
+
+        let (mut part1, mut part2) = (0, 0);
+
+        for y in 0..grid.len() as i64 {
+            for x in 0..grid[0].len() as i64 {
+                // How many times the spacing between `a` and `b` the location is away from `a`
+                let steps = |(ax, ay): (i64, i64), (bx, by): (i64, i64)| {
+                    let (dx, dy) = (bx - ax, by - ay);
+                    let k = if dx != 0 {
+                        (x - ax) / dx
+                    } else {
+                        (y - ay) / dy
+                    };
+                    (x - ax == k * dx && y - ay == k * dy).then_some(k)
+                };
+
+                let steps = pairs
+                    .iter()
+                    .filter_map(|(a, b)| steps(*a, *b))
+                    .collect::<Vec<_>>();
+
+                if steps.iter().any(|k| *k == -1 || *k == 2) {
+                    part1 += 1;
+                }
+
+                if !steps.is_empty() {
+                    part2 += 1;
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+}
+
+pub mod day9 {
+    /// Compacts the disk block by block, and then file by file, on a fully expanded disk.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let mut disk: Vec<Option<usize>> = Vec::new();
+
+        for (index, length) in input.trim().chars().enumerate() {
+            let block = (index % 2 == 0).then_some(index / 2);
+            disk.extend(std::iter::repeat_n(
+                block,
+                length.to_digit(10).unwrap() as usize,
+            ));
+        }
+
+        let checksum = |disk: &[Option<usize>]| {
+            disk.iter()
+                .enumerate()
+                .map(|(index, block)| index * block.unwrap_or(0))
+                .sum()
+        };
+
+        let mut blocks = disk.clone();
+        loop {
+            let first_free = blocks.iter().position(Option::is_none).unwrap();
+            let last_used = blocks.iter().rposition(Option::is_some).unwrap();
+            if first_free > last_used {
+                break;
+            }
+            blocks.swap(first_free, last_used);
+        }
+
+        let files = disk.iter().flatten().max().map_or(0, |id| id + 1);
+        for id in (0..files).rev() {
+            // Empty files keep their id but have nothing to move
+            let Some(start) = disk.iter().position(|block| *block == Some(id)) else {
+                continue;
+            };
+            let length = disk.iter().filter(|block| **block == Some(id)).count();
+
+            let free =
+                (0..start).find(|&index| disk[index..index + length].iter().all(Option::is_none));
+
+            if let Some(free) = free {
+                disk[free..free + length].fill(Some(id));
+                disk[start..start + length].fill(None);
+            }
+        }
+
+        (checksum(&blocks), checksum(&disk))
+    }
+}
+
+pub mod day10 {
+    use super::grid;
+    use std::collections::HashSet;
+
+    /// Follows every hiking trail from every trail head.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let grid = grid(input);
+        let (mut part1, mut part2) = (0, 0);
+
+        fn walk(
+            grid: &[Vec<char>],
+            x: usize,
+            y: usize,
+            peaks: &mut HashSet<(usize, usize)>,
+        ) -> usize {
+            if grid[y][x] == '9' {
+                peaks.insert((x, y));
+                return 1;
+            }
+
+            let next = (grid[y][x] as u8 + 1) as char;
+            let mut trails = 0;
+
+            for (nx, ny) in [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ] {
+                if grid.get(ny).and_then(|row| row.get(nx)) == Some(&next) {
+                    trails += walk(grid, nx, ny, peaks);
+                }
+            }
+
+            trails
+        }
+
+        for y in 0..grid.len() {
+            for x in 0..grid[y].len() {
+                if grid[y][x] == '0' {
+                    let mut peaks = HashSet::new();
+                    part2 += walk(&grid, x, y, &mut peaks);
+                    part1 += peaks.len();
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+}
+
+pub mod day11 {
+    use super::numbers;
+    use std::collections::HashMap;
+
+    /// Blinks by keeping a count per engraved number, applying the rules to every one of them.
+    fn blink(input: &str, blinks: usize) -> usize {
+        let mut stones: HashMap<usize, usize> = HashMap::new();
+        for stone in numbers(input) {
+            *stones.entry(stone).or_default() += 1;
+        }
+
+        for _ in 0..blinks {
+            let mut next = HashMap::new();
+
+            for (stone, count) in stones {
+                let digits = stone.checked_ilog10().map_or(1, |log| log + 1);
+
+                let replacements = if stone == 0 {
+                    vec![1]
+                } else if digits % 2 == 0 {
+                    let half = 10usize.pow(digits / 2);
+                    vec![stone / half, stone % half]
+                } else {
+                    vec![stone * 2024]
+                };
+
+                for replacement in replacements {
+                    *next.entry(replacement).or_default() += count;
+                }
+            }
+
+            stones = next;
+        }
+
+        stones.values().sum()
+    }
+
+    pub fn solve(input: &str) -> (usize, usize) {
+        (blink(input, 25), blink(input, 75))
+    }
+}
+
+pub mod day16 {
+    use super::grid;
+
+    const DELTAS: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+    /// Relaxes every move until nothing improves, starting from each of the given states.
+    fn distances(
+        grid: &[Vec<char>],
+        starts: &[(usize, usize, usize)],
+        reverse: bool,
+    ) -> Vec<Vec<[usize; 4]>> {
+        let mut distances = vec![vec![[usize::MAX; 4]; grid[0].len()]; grid.len()];
+        for &(x, y, direction) in starts {
+            distances[y][x][direction] = 0;
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for y in 0..grid.len() {
+                for x in 0..grid[0].len() {
+                    for direction in 0..4 {
+                        let distance = distances[y][x][direction];
+                        if grid[y][x] == '#' || distance == usize::MAX {
+                            continue;
+                        }
+
+                        let (dx, dy) = DELTAS[direction];
+                        let (dx, dy) = if reverse { (-dx, -dy) } else { (dx, dy) };
+                        let (nx, ny) = ((x as i64 + dx) as usize, (y as i64 + dy) as usize);
+
+                        let mut moves = vec![
+                            (x, y, (direction + 1) % 4, 1000),
+                            (x, y, (direction + 3) % 4, 1000),
+                        ];
+                        if grid[ny][nx] != '#' {
+                            moves.push((nx, ny, direction, 1));
+                        }
+
+                        for (nx, ny, next, cost) in moves {
+                            if distance + cost < distances[ny][nx][next] {
+                                distances[ny][nx][next] = distance + cost;
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The lowest score, and the tiles where the scores from the start and to the end add up to it.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let grid = grid(input);
+        let find = |tile| {
+            (0..grid.len())
+                .flat_map(|y| (0..grid[0].len()).map(move |x| (x, y)))
+                .find(|&(x, y)| grid[y][x] == tile)
+                .unwrap()
+        };
+
+        let (sx, sy) = find('S');
+        let (ex, ey) = find('E');
+
+        let from_start = distances(&grid, &[(sx, sy, 1)], false);
+        let best = *from_start[ey][ex].iter().min().unwrap();
+        if best == usize::MAX {
+            return (usize::MAX, 0);
+        }
+
+        let ends = (0..4)
+            .filter(|&direction| from_start[ey][ex][direction] == best)
+            .map(|direction| (ex, ey, direction))
+            .collect::<Vec<_>>();
+        let to_end = distances(&grid, &ends, true);
+
+        let tiles = (0..grid.len())
+            .flat_map(|y| (0..grid[0].len()).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                (0..4).any(|direction| {
+                    let (a, b) = (from_start[y][x][direction], to_end[y][x][direction]);
+                    a != usize::MAX && b != usize::MAX && a + b == best
+                })
+            })
+            .count();
+
+        (best, tiles)
+    }
+}
+
+pub mod day19 {
+    /// Counts the arrangements of every design by trying every pattern at every position.
+    pub fn solve(input: &str) -> (usize, usize) {
+        let (patterns, designs) = input.split_once("\n\n").unwrap();
+        let patterns = patterns.split(", ").collect::<Vec<_>>();
+
+        let (mut part1, mut part2) = (0, 0);
+
+        for design in designs.lines() {
+            let mut ways = vec![0usize; design.len() + 1];
+            ways[0] = 1;
+
+            for end in 1..=design.len() {
+                ways[end] = patterns
+                    .iter()
+                    .filter(|pattern| design[..end].ends_with(*pattern))
+                    .map(|pattern| ways[end - pattern.len()])
+                    .sum();
+            }
+
+            part1 += usize::from(ways[design.len()] > 0);
+            part2 += ways[design.len()];
+        }
+
+        (part1, part2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{Rng, Size};
+    use crate::{find_day, Answer, Part};
+
+    /// Compares the registered solver of the day to the reference on `cases` small generated inputs
+    /// of varying size.
+    fn check(day: u8, cases: u64, max_count: usize, reference: fn(&str) -> (usize, usize)) {
+        let solution = find_day(day).unwrap();
+        let mut rng = Rng::with_seed(day as u64);
+
+        for seed in 0..cases {
+            let size = Size {
+                width: rng.usize(3..=12),
+                height: rng.usize(3..=12),
+                count: rng.usize(1..=max_count),
+            };
+            let input = solution.generate(seed, &size).unwrap();
+            let (part1, part2) = reference(&input);
+
+            for (part, expected) in [(Part::One, part1), (Part::Two, part2)] {
+                let actual = solution.solve(&input, part).unwrap();
+                assert_eq!(
+                    Some(Answer::from(expected)),
+                    actual,
+                    "day {} {} on seed {}:\n{}",
+                    day,
+                    part,
+                    seed,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_day2() {
+        check(2, 2000, 10, day2::solve);
+    }
+
+    #[test]
+    fn test_day5() {
+        check(5, 500, 10, day5::solve);
+    }
+
+    #[test]
+    fn test_day7() {
+        check(7, 1000, 5, day7::solve);
+    }
+
+    #[test]
+    fn test_day8() {
+        check(8, 1000, 1, day8::solve);
+    }
+
+    #[test]
+    fn test_day9() {
+        check(9, 2000, 40, day9::solve);
+    }
+
+    #[test]
+    fn test_day10() {
+        check(10, 2000, 1, day10::solve);
+    }
+
+    #[test]
+    fn test_day11() {
+        check(11, 50, 3, day11::solve);
+    }
+
+    #[test]
+    fn test_day16() {
+        check(16, 500, 1, day16::solve);
+    }
+
+    #[test]
+    fn test_day19() {
+        check(19, 1000, 5, day19::solve);
+    }
+}
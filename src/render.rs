@@ -0,0 +1,253 @@
+use crate::grid::Grid;
+use std::fmt::Write;
+
+/// A colour as red, green and blue intensities.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const DARK_GREY: Self = Self::rgb(64, 64, 64);
+    pub const GREY: Self = Self::rgb(128, 128, 128);
+    pub const GOLD: Self = Self::rgb(255, 200, 0);
+    pub const GREEN: Self = Self::rgb(0, 200, 80);
+    pub const RED: Self = Self::rgb(220, 40, 40);
+    pub const BLUE: Self = Self::rgb(40, 100, 230);
+
+    /// Colours that are easy to tell apart, for things like antenna frequencies.
+    const PALETTE: [Self; 8] = [
+        Self::rgb(230, 25, 75),
+        Self::rgb(60, 180, 75),
+        Self::rgb(255, 225, 25),
+        Self::rgb(0, 130, 200),
+        Self::rgb(245, 130, 48),
+        Self::rgb(145, 30, 180),
+        Self::rgb(70, 240, 240),
+        Self::rgb(240, 50, 230),
+    ];
+
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// The `index`th colour of a small palette, repeating once it runs out.
+    pub fn palette(index: usize) -> Self {
+        Self::PALETTE[index % Self::PALETTE.len()]
+    }
+
+    /// A shade from black at 0 to white at 1.
+    pub fn grey(brightness: f32) -> Self {
+        let level = (brightness.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::rgb(level, level, level)
+    }
+}
+
+/// A single cell of a [`Picture`]: the character shown for it in the terminal and its colours.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Pixel {
+    pub symbol: char,
+    pub color: Color,
+    /// Set by overlays. Images fill highlighted cells with it instead of the foreground colour.
+    pub background: Option<Color>,
+}
+
+impl Pixel {
+    pub fn new(symbol: char, color: Color) -> Self {
+        Self {
+            symbol,
+            color,
+            background: None,
+        }
+    }
+}
+
+/// How highlighted cells are drawn over a picture, e.g. the tiles on a best path.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Overlay {
+    pub background: Color,
+    /// Replaces the symbol of the cell, keeps it if `None`.
+    pub symbol: Option<char>,
+}
+
+/// A grid of coloured cells that can be drawn to the terminal or saved as an image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Picture {
+    grid: Grid<Pixel>,
+}
+
+impl Picture {
+    pub fn new(width: usize, height: usize, mut pixel: impl FnMut(usize, usize) -> Pixel) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| pixel(x, y))
+            .collect();
+
+        Self {
+            grid: Grid::new(width, height, cells),
+        }
+    }
+
+    /// Draws every cell of the grid on its own, cells of the picture have the same indices.
+    pub fn from_grid<T>(grid: &Grid<T>, mut pixel: impl FnMut(&T) -> Pixel) -> Self {
+        Self {
+            grid: Grid::new(
+                grid.width(),
+                grid.height(),
+                grid.cells().iter().map(&mut pixel).collect(),
+            ),
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<Pixel> {
+        &self.grid
+    }
+
+    /// Highlights the cells at the given indices, later overlays are drawn over earlier ones.
+    pub fn highlight(&mut self, cells: impl IntoIterator<Item = usize>, overlay: Overlay) {
+        for index in cells {
+            let pixel = &mut self.grid[index];
+            pixel.background = Some(overlay.background);
+            pixel.symbol = overlay.symbol.unwrap_or(pixel.symbol);
+        }
+    }
+
+    /// The picture as text with 24 bit colour escape codes, one line per row.
+    pub fn to_ansi(&self) -> String {
+        let mut output = String::new();
+
+        for row in self.grid.rows() {
+            let mut style = None;
+
+            for pixel in row {
+                if style != Some((pixel.color, pixel.background)) {
+                    style = Some((pixel.color, pixel.background));
+
+                    let Color { red, green, blue } = pixel.color;
+                    write!(output, "\x1b[0;38;2;{};{};{}", red, green, blue).unwrap();
+
+                    if let Some(Color { red, green, blue }) = pixel.background {
+                        write!(output, ";48;2;{};{};{}", red, green, blue).unwrap();
+                    }
+
+                    output.push('m');
+                }
+
+                output.push(pixel.symbol);
+            }
+
+            output.push_str("\x1b[0m\n");
+        }
+
+        output
+    }
+
+    /// The picture as a binary PPM image, with every cell a square of `scale` by `scale` pixels.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = (self.grid.width() * scale, self.grid.height() * scale);
+
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        image.reserve(width * height * 3);
+
+        for row in self.grid.rows() {
+            let mut line = Vec::with_capacity(width * 3);
+
+            for pixel in row {
+                let Color { red, green, blue } = pixel.background.unwrap_or(pixel.color);
+
+                for _ in 0..scale {
+                    line.extend([red, green, blue]);
+                }
+            }
+
+            for _ in 0..scale {
+                image.extend(&line);
+            }
+        }
+
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_day, DAYS};
+
+    fn picture() -> Picture {
+        let mut picture = Picture::new(2, 1, |x, _| match x {
+            0 => Pixel::new('#', Color::GREY),
+            _ => Pixel::new('.', Color::BLACK),
+        });
+
+        let overlay = Overlay {
+            background: Color::GOLD,
+            symbol: Some('O'),
+        };
+        picture.highlight([1], overlay);
+
+        picture
+    }
+
+    #[test]
+    fn test_ansi() {
+        assert_eq!(
+            "\x1b[0;38;2;128;128;128m#\x1b[0;38;2;0;0;0;48;2;255;200;0mO\x1b[0m\n",
+            picture().to_ansi()
+        );
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = picture().to_ppm(2);
+        let header = b"P6\n4 2\n255\n";
+
+        assert_eq!(header, &image[..header.len()]);
+        assert_eq!(header.len() + 4 * 2 * 3, image.len());
+        assert_eq!(
+            [128, 128, 128, 128, 128, 128, 255, 200, 0, 255, 200, 0],
+            image[header.len()..header.len() + 12]
+        );
+    }
+
+    /// An empty input draws an empty picture.
+    #[test]
+    fn test_render_empty() {
+        let solution = find_day(8).unwrap();
+        let picture = solution.render(&*solution.parse("").unwrap()).unwrap();
+
+        assert_eq!("", picture.to_ansi());
+        assert_eq!(b"P6\n0 0\n255\n", &picture.to_ppm(2)[..]);
+    }
+
+    /// Days that can render draw a picture as large as their example grid.
+    #[test]
+    fn test_render_examples() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        for day in [8, 10, 16] {
+            let solution = find_day(day).unwrap();
+            let input = std::fs::read_to_string(root.join(solution.examples()[0])).unwrap();
+            let picture = solution.render(&*solution.parse(&input).unwrap()).unwrap();
+
+            let lines = input.lines().filter(|line| !line.is_empty());
+            assert_eq!(lines.count(), picture.grid().height());
+            assert_eq!(input.find('\n'), Some(picture.grid().width()));
+            assert_eq!(picture.grid().height(), picture.to_ansi().lines().count());
+        }
+
+        let others = DAYS
+            .iter()
+            .filter(|solution| ![8, 10, 16].contains(&solution.day()))
+            .filter_map(|solution| {
+                let input = solution.generate(0, &crate::generate::Size::square(5))?;
+                Some((solution, input))
+            });
+        for (solution, input) in others {
+            assert!(solution.render(&*solution.parse(&input).unwrap()).is_none());
+        }
+    }
+}
@@ -0,0 +1,775 @@
+use crate::render::Picture;
+use crate::stats;
+use crate::{Answer, DynSolution, Part};
+use cpu_time::ThreadTime;
+use serde::Serialize;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Where the runner reads the puzzle input of a day from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// `inputs/dayN.txt`, the real input downloaded for the day.
+    Inputs,
+    Path(PathBuf),
+    Stdin,
+    /// Every example listed by the day.
+    Examples,
+}
+
+/// A single puzzle input together with where it came from.
+#[derive(Clone)]
+pub struct Input {
+    /// Path of the input, or `-` for stdin.
+    pub label: String,
+    pub text: String,
+}
+
+impl Source {
+    /// Reads every input of the source for a day, or describes why that was not possible.
+    pub fn read(&self, solution: &dyn DynSolution) -> Result<Vec<Input>, String> {
+        let read_file = |path: &Path| {
+            let label = path.display().to_string();
+            std::fs::read_to_string(path)
+                .map(|text| Input {
+                    label: label.clone(),
+                    text,
+                })
+                .map_err(|error| format!("cannot read {}: {}", label, error))
+        };
+
+        match self {
+            Source::Inputs => {
+                read_file(&Path::new("inputs").join(format!("day{}.txt", solution.day())))
+                    .map(|input| vec![input])
+            }
+            Source::Path(path) => read_file(path).map(|input| vec![input]),
+            Source::Stdin => std::io::read_to_string(std::io::stdin())
+                .map(|text| {
+                    vec![Input {
+                        label: "-".to_string(),
+                        text,
+                    }]
+                })
+                .map_err(|error| format!("cannot read stdin: {}", error)),
+            Source::Examples => {
+                if solution.examples().is_empty() {
+                    return Err("no examples".to_string());
+                }
+
+                solution
+                    .examples()
+                    .iter()
+                    .map(|example| read_file(Path::new(example)))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Outcome of parsing an input or solving a part of it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    /// There was nothing to run, e.g. the input is missing or the day has no such part.
+    Skipped,
+    /// The input was rejected by the parser, or the part has no answer for it.
+    Error,
+    Panicked,
+    /// The time budget of the run was exceeded.
+    #[serde(rename = "timeout")]
+    TimedOut,
+}
+
+/// Answer and timing of solving a single part.
+pub struct PartRun {
+    pub part: Part,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    pub message: Option<String>,
+    pub solve_time: Option<Duration>,
+    /// Counts of the day's counters while solving, empty without the `stats` feature.
+    pub counters: Vec<(&'static str, u64)>,
+}
+
+/// Answers and timings of a single day on a single input.
+pub struct DayRun {
+    pub day: u8,
+    pub title: &'static str,
+    /// Where the input came from, `None` if there is no input at all.
+    pub input: Option<String>,
+    pub input_hash: Option<u64>,
+    /// `Ok` if the input was parsed, the parts are only solved in that case.
+    pub status: Status,
+    pub message: Option<String>,
+    pub parse_time: Option<Duration>,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    /// A day that could not be run at all, e.g. because its input is missing.
+    pub fn skipped(day: u8, title: &'static str, reason: String) -> Self {
+        Self {
+            day,
+            title,
+            input: None,
+            input_hash: None,
+            status: Status::Skipped,
+            message: Some(reason),
+            parse_time: None,
+            parts: Vec::new(),
+        }
+    }
+}
+
+/// 64 bit FNV-1a hash of the input, stable across platforms and compiler versions so it can be
+/// compared between runs.
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+thread_local! {
+    /// Whether a run on this thread catches panics, see [`catch`].
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// Location of the last panic on this thread.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Wraps the panic hook so that a panic caught by a run keeps its location for the run to report,
+/// instead of being printed to stderr in the middle of the output. Any other panic, e.g. in
+/// `main`, still goes to the previous hook.
+pub fn capture_panics() {
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        if CAPTURING.with(Cell::get) {
+            let location = info.location().map(ToString::to_string);
+            LAST_PANIC.with(|last| *last.borrow_mut() = location);
+        } else {
+            previous(info);
+        }
+    }));
+}
+
+/// Calls `f`, turning a panic into its message.
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let outer = CAPTURING.with(|capturing| capturing.replace(true));
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CAPTURING.with(|capturing| capturing.set(outer));
+
+    result.map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    };
+
+    match LAST_PANIC.with(|last| last.borrow_mut().take()) {
+        Some(location) => format!("{} at {}", message, location),
+        None => message,
+    }
+}
+
+impl DayRun {
+    fn new(solution: &dyn DynSolution, input: &Input) -> Self {
+        Self {
+            day: solution.day(),
+            title: solution.title(),
+            input: Some(input.label.clone()),
+            input_hash: Some(input_hash(&input.text)),
+            status: Status::Ok,
+            message: None,
+            parse_time: None,
+            parts: Vec::new(),
+        }
+    }
+}
+
+type Parsed = Result<(Box<dyn Any + Send + Sync>, Duration), (Status, String)>;
+
+fn parse(solution: &dyn DynSolution, input: &str) -> Parsed {
+    let start = Instant::now();
+
+    match catch(|| solution.parse(input)) {
+        Ok(Ok(parsed)) => Ok((parsed, start.elapsed())),
+        Ok(Err(error)) => Err((Status::Error, error.to_string())),
+        Err(message) => Err((Status::Panicked, message)),
+    }
+}
+
+fn solve(solution: &dyn DynSolution, parsed: &dyn Any, part: Part) -> PartRun {
+    stats::reset(solution.counters());
+
+    let start = Instant::now();
+    let answer = catch(|| match part {
+        Part::One => Some(solution.part1(parsed)),
+        Part::Two if !solution.has_part2() => None,
+        Part::Two => solution.part2(parsed),
+    });
+    let solve_time = start.elapsed();
+
+    let (status, answer, message) = match answer {
+        Ok(Some(Ok(answer))) => (Status::Ok, Some(answer), None),
+        Ok(Some(Err(error))) => (Status::Error, None, Some(error.to_string())),
+        Ok(None) => (Status::Skipped, None, Some("not implemented".to_string())),
+        Err(message) => (Status::Panicked, None, Some(message)),
+    };
+
+    PartRun {
+        part,
+        status,
+        solve_time: (status == Status::Ok).then_some(solve_time),
+        answer,
+        message,
+        counters: stats::snapshot(solution.counters()),
+    }
+}
+
+/// Parses the input and draws the day's picture of it, `None` if the day cannot render.
+pub fn render(
+    solution: &dyn DynSolution,
+    input: &Input,
+) -> Result<Option<Picture>, (Status, String)> {
+    let (parsed, _) = parse(solution, &input.text)?;
+
+    catch(|| solution.render(&*parsed)).map_err(|message| (Status::Panicked, message))
+}
+
+/// Parses the input once and solves each of the requested parts on it. A panicking solver is
+/// recorded as such for its part instead of aborting the run.
+pub fn run(solution: &dyn DynSolution, input: &Input, parts: &[Part]) -> DayRun {
+    let mut run = DayRun::new(solution, input);
+
+    let parsed = match parse(solution, &input.text) {
+        Ok((parsed, parse_time)) => {
+            run.parse_time = Some(parse_time);
+            parsed
+        }
+        Err((status, message)) => {
+            run.status = status;
+            run.message = Some(message);
+            return run;
+        }
+    };
+
+    for part in parts {
+        run.parts.push(solve(solution, &*parsed, *part));
+    }
+
+    run
+}
+
+/// Progress reported by the worker thread of [`run_with_budget`].
+enum Event {
+    Parsed(Result<Duration, (Status, String)>),
+    Solved(PartRun),
+}
+
+/// Like [`run`], but gives parsing and every part at most `budget` to finish. The day runs on its
+/// own thread, which is abandoned as soon as it overruns; a solver stuck in an endless loop keeps
+/// that thread busy until the process exits, but doesn't hold up the rest of the run.
+pub fn run_with_budget(
+    solution: &'static dyn DynSolution,
+    input: &Input,
+    parts: &[Part],
+    budget: Duration,
+) -> DayRun {
+    let mut run = DayRun::new(solution, input);
+    let (sender, receiver) = mpsc::channel();
+
+    let text = input.text.clone();
+    let worker_parts = parts.to_vec();
+    std::thread::spawn(move || {
+        let parsed = match parse(solution, &text) {
+            Ok((parsed, parse_time)) => {
+                let _ = sender.send(Event::Parsed(Ok(parse_time)));
+                parsed
+            }
+            Err(error) => {
+                let _ = sender.send(Event::Parsed(Err(error)));
+                return;
+            }
+        };
+
+        for part in worker_parts {
+            if sender
+                .send(Event::Solved(solve(solution, &*parsed, part)))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+
+    match receiver.recv_timeout(budget) {
+        Ok(Event::Parsed(Ok(parse_time))) => run.parse_time = Some(parse_time),
+        Ok(Event::Parsed(Err((status, message)))) => {
+            run.status = status;
+            run.message = Some(message);
+            return run;
+        }
+        Ok(Event::Solved(_)) | Err(_) => {
+            run.status = Status::TimedOut;
+            run.message = Some(format!("parsing took longer than {:?}", budget));
+            return run;
+        }
+    }
+
+    let mut timed_out = false;
+
+    for part in parts {
+        let event = if timed_out {
+            None
+        } else {
+            receiver.recv_timeout(budget).ok()
+        };
+
+        let part_run = match event {
+            Some(Event::Solved(part_run)) => part_run,
+            _ => {
+                let message = if timed_out {
+                    "an earlier part timed out".to_string()
+                } else {
+                    format!("solving took longer than {:?}", budget)
+                };
+                timed_out = true;
+
+                PartRun {
+                    part: *part,
+                    status: Status::TimedOut,
+                    answer: None,
+                    message: Some(message),
+                    solve_time: None,
+                    counters: Vec::new(),
+                }
+            }
+        };
+
+        run.parts.push(part_run);
+    }
+
+    run
+}
+
+/// Parses every input once and then solves every part of it as an independent job, both on a pool
+/// of threads, one per available core. Returns the runs in the order of the inputs and the CPU time
+/// of the jobs, summed over their threads. Jobs abandoned for running over the budget don't count.
+pub fn run_parallel(
+    inputs: &[(&'static dyn DynSolution, Input)],
+    parts: &[Part],
+    budget: Option<Duration>,
+) -> (Vec<DayRun>, Duration) {
+    let shared = Arc::new(inputs.to_vec());
+    let mut cpu_time = Duration::ZERO;
+
+    let jobs = Arc::clone(&shared);
+    let parsed = run_pool(inputs.len(), budget, move |index| {
+        let (solution, input) = &jobs[index];
+        parse(*solution, &input.text)
+    });
+
+    let mut runs = inputs
+        .iter()
+        .map(|(solution, input)| DayRun::new(*solution, input))
+        .collect::<Vec<_>>();
+    let mut inputs = Vec::with_capacity(runs.len());
+
+    for (run, parsed) in runs.iter_mut().zip(parsed) {
+        match parsed {
+            Some((Ok((parsed, parse_time)), time)) => {
+                run.parse_time = Some(parse_time);
+                cpu_time += time;
+                inputs.push(Some(parsed));
+            }
+            Some((Err((status, message)), time)) => {
+                run.status = status;
+                run.message = Some(message);
+                cpu_time += time;
+                inputs.push(None);
+            }
+            None => {
+                run.status = Status::TimedOut;
+                run.message = Some(format!(
+                    "parsing took longer than {:?}",
+                    budget.expect("only a budget times out")
+                ));
+                inputs.push(None);
+            }
+        }
+    }
+
+    let jobs = (0..inputs.len())
+        .filter(|input| inputs[*input].is_some())
+        .flat_map(|input| parts.iter().map(move |part| (input, *part)))
+        .collect::<Vec<_>>();
+    let parsed = Arc::new(inputs);
+
+    let pool_jobs = jobs.clone();
+    let solved = run_pool(jobs.len(), budget, move |index| {
+        let (input, part) = pool_jobs[index];
+        let parsed = parsed[input]
+            .as_deref()
+            .expect("only parsed inputs are solved");
+        solve(shared[input].0, parsed, part)
+    });
+
+    for ((input, part), solved) in jobs.into_iter().zip(solved) {
+        let part_run = match solved {
+            Some((part_run, time)) => {
+                cpu_time += time;
+                part_run
+            }
+            None => PartRun {
+                part,
+                status: Status::TimedOut,
+                answer: None,
+                message: Some(format!(
+                    "solving took longer than {:?}",
+                    budget.expect("only a budget times out")
+                )),
+                solve_time: None,
+                counters: Vec::new(),
+            },
+        };
+
+        runs[input].parts.push(part_run);
+    }
+
+    (runs, cpu_time)
+}
+
+/// Progress reported by the threads of [`run_pool`].
+enum PoolEvent<T> {
+    Started(usize, Instant),
+    /// The result and the CPU time the job took on its thread.
+    Finished(usize, T, Duration),
+}
+
+/// Calls `job` with every index below `count` on a pool of threads, one per available core, and
+/// returns the results in the order of the indices with the CPU time each job took. A job that runs
+/// longer than the budget is reported as `None` and abandoned: like in [`run_with_budget`], its
+/// thread keeps running until the process exits. A new thread takes its place in the pool.
+fn run_pool<T: Send + 'static>(
+    count: usize,
+    budget: Option<Duration>,
+    job: impl Fn(usize) -> T + Send + Sync + 'static,
+) -> Vec<Option<(T, Duration)>> {
+    let job = Arc::new(job);
+    let next_job = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::channel();
+
+    let spawn_worker = || {
+        let (job, next_job, sender) = (Arc::clone(&job), Arc::clone(&next_job), sender.clone());
+
+        std::thread::spawn(move || loop {
+            let index = next_job.fetch_add(1, Ordering::Relaxed);
+            if index >= count {
+                break;
+            }
+
+            let _ = sender.send(PoolEvent::Started(index, Instant::now()));
+            let start = ThreadTime::now();
+            let result = job(index);
+            let _ = sender.send(PoolEvent::Finished(index, result, start.elapsed()));
+        });
+    };
+
+    let threads = std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(count);
+    for _ in 0..threads {
+        spawn_worker();
+    }
+
+    let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+    let mut running = vec![None; count];
+    let mut remaining = count;
+
+    while remaining > 0 {
+        let deadline = budget.and_then(|budget| {
+            running
+                .iter()
+                .flatten()
+                .min()
+                .map(|start: &Instant| *start + budget)
+        });
+
+        let event = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(PoolEvent::Started(index, start)) => running[index] = Some(start),
+            Ok(PoolEvent::Finished(index, result, time)) => {
+                if running[index].take().is_some() {
+                    results[index] = Some(Some((result, time)));
+                    remaining -= 1;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let budget = budget.expect("only a budget times out");
+
+                for index in 0..count {
+                    if running[index].is_some_and(|start| start.elapsed() >= budget) {
+                        running[index] = None;
+                        results[index] = Some(None);
+                        remaining -= 1;
+                        spawn_worker();
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("the pool keeps a sender"),
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every job has finished or timed out"))
+        .collect()
+}
+
+/// A single day and part of a run, the unit of the JSON output.
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    title: &'a str,
+    part: u8,
+    input: Option<&'a str>,
+    /// Hex encoded, as JSON numbers can't hold every 64 bit value exactly.
+    input_hash: Option<String>,
+    status: Status,
+    answer: Option<String>,
+    message: Option<&'a str>,
+    parse_ns: Option<u128>,
+    solve_ns: Option<u128>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    counters: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    year: u16,
+    results: Vec<Record<'a>>,
+}
+
+/// Renders the runs as a JSON document with one record per day and part:
+///
+/// ```json
+/// {"year": 2024, "results": [{"day": 1, "title": "Historian Hysteria", "part": 1,
+///   "input": "inputs/day1.txt", "input_hash": "8c9a...", "status": "ok", "answer": "11",
+///   "message": null, "parse_ns": 5120, "solve_ns": 830}]}
+/// ```
+///
+/// Days that could not be parsed or run get a record per requested part carrying the status and
+/// message of the day. With the `stats` feature, records also carry a `counters` object.
+pub fn to_json(runs: &[DayRun], parts: &[Part]) -> String {
+    let mut results = Vec::new();
+
+    for run in runs {
+        let input_hash = run.input_hash.map(|hash| format!("{:016x}", hash));
+        let record = |part: Part| Record {
+            day: run.day,
+            title: run.title,
+            part: part.number(),
+            input: run.input.as_deref(),
+            input_hash: input_hash.clone(),
+            status: run.status,
+            answer: None,
+            message: run.message.as_deref(),
+            parse_ns: run.parse_time.map(|time| time.as_nanos()),
+            solve_ns: None,
+            counters: BTreeMap::new(),
+        };
+
+        if run.status != Status::Ok {
+            results.extend(parts.iter().map(|part| record(*part)));
+            continue;
+        }
+
+        results.extend(run.parts.iter().map(|part| Record {
+            status: part.status,
+            answer: part.answer.as_ref().map(ToString::to_string),
+            message: part.message.as_deref(),
+            solve_ns: part.solve_time.map(|time| time.as_nanos()),
+            counters: part.counters.iter().copied().collect(),
+            ..record(part.part)
+        }));
+    }
+
+    let document = Document {
+        year: 2024,
+        results,
+    };
+
+    serde_json::to_string(&document).expect("records always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_day, ParseError, Solution, SolveError};
+
+    fn input(text: &str) -> Input {
+        Input {
+            label: "test".to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let solution = find_day(1).unwrap();
+
+        let run = run(solution, &input("3   4\n1   3\n"), &[Part::One, Part::Two]);
+        assert_eq!(Status::Ok, run.status);
+        assert_eq!(Some(Answer::Unsigned(3)), run.parts[0].answer);
+
+        let run = super::run(solution, &input("3 4\n"), &[Part::One]);
+        assert_eq!(Status::Error, run.status);
+        assert!(run.parts.is_empty());
+
+        let maze = input("#####\n#S#E#\n#####\n");
+        let run = super::run(find_day(16).unwrap(), &maze, &[Part::One]);
+        assert_eq!(Status::Ok, run.status);
+        assert_eq!(Status::Error, run.parts[0].status);
+        assert_eq!(None, run.parts[0].answer);
+    }
+
+    /// A day whose first part never finishes in time and whose second part panics.
+    struct Stuck;
+
+    impl Solution for Stuck {
+        const DAY: u8 = 0;
+        const TITLE: &'static str = "Stuck";
+        const EXAMPLES: &'static [&'static str] = &[];
+
+        type Input = ();
+        type Output = usize;
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input) -> Result<Self::Output, SolveError> {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(1)
+        }
+
+        fn part2(_input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+            panic!("no second part")
+        }
+    }
+
+    #[test]
+    fn test_run_with_budget() {
+        let run = super::run(&Stuck, &input(""), &[Part::Two]);
+        assert_eq!(Status::Panicked, run.parts[0].status);
+
+        let run = run_with_budget(
+            &Stuck,
+            &input(""),
+            &[Part::One, Part::Two],
+            Duration::from_millis(100),
+        );
+        assert_eq!(Status::Ok, run.status);
+        assert_eq!(Status::TimedOut, run.parts[0].status);
+        assert_eq!(Status::TimedOut, run.parts[1].status);
+    }
+
+    /// The hook is global to the process, so it is installed in a test process of its own, which
+    /// leaves the panics of the other tests alone. Only the panic outside a run reaches stderr.
+    #[test]
+    fn test_capture_panics() {
+        if std::env::var_os("CAPTURE_PANICS").is_none() {
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "runner::tests::test_capture_panics",
+                    "--exact",
+                    "--nocapture",
+                ])
+                .env("CAPTURE_PANICS", "1")
+                .output()
+                .unwrap();
+            let stderr = String::from_utf8_lossy(&output.stderr);
+
+            assert!(output.status.success(), "{}", stderr);
+            assert!(stderr.contains("outside a run"), "{}", stderr);
+            assert!(!stderr.contains("inside a run"), "{}", stderr);
+            return;
+        }
+
+        capture_panics();
+
+        let message = catch(|| panic!("inside a run")).unwrap_err();
+        assert!(message.starts_with("inside a run at src/runner.rs:"));
+
+        assert!(catch_unwind(|| panic!("outside a run")).is_err());
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let inputs = [
+            (find_day(1).unwrap(), input("3   4\n1   3\n")),
+            (find_day(1).unwrap(), input("3 4\n")),
+            (
+                find_day(25).unwrap(),
+                input("#####\n.....\n.....\n.....\n.....\n.....\n.....\n"),
+            ),
+        ];
+
+        let (runs, _) = run_parallel(&inputs, &[Part::One, Part::Two], None);
+
+        assert_eq!(3, runs.len());
+        assert_eq!(Some(Answer::Unsigned(3)), runs[0].parts[1].answer);
+        assert_eq!(Status::Error, runs[1].status);
+        assert_eq!(Status::Skipped, runs[2].parts[1].status);
+
+        let budget = Some(Duration::from_millis(100));
+        let (runs, _) = run_parallel(&[(&Stuck, input(""))], &[Part::One, Part::Two], budget);
+        assert_eq!(Status::Ok, runs[0].status);
+        assert_eq!(Status::TimedOut, runs[0].parts[0].status);
+        assert_eq!(Status::Panicked, runs[0].parts[1].status);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let solution = find_day(25).unwrap();
+        let parts = [Part::One, Part::Two];
+        let runs = [
+            run(
+                solution,
+                &input("#####\n.....\n.....\n.....\n.....\n.....\n.....\n"),
+                &parts,
+            ),
+            DayRun::skipped(6, "", "not implemented".to_string()),
+        ];
+
+        let json: serde_json::Value = serde_json::from_str(&to_json(&runs, &parts)).unwrap();
+        let results = json["results"].as_array().unwrap();
+
+        assert_eq!(4, results.len());
+        assert_eq!("ok", results[0]["status"]);
+        assert_eq!("skipped", results[1]["status"]);
+        assert_eq!("not implemented", results[3]["message"]);
+        assert_eq!(16, results[0]["input_hash"].as_str().unwrap().len());
+    }
+}
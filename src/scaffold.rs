@@ -0,0 +1,248 @@
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+const FUZZ_TEMPLATE: &str = include_str!("../templates/fuzz_target.rs");
+
+/// Source of a new day's module, with stub solvers, no input generator and a stub test on its
+/// example.
+pub fn render(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title}}", &format!("{:?}", title))
+}
+
+/// Source of a new day's fuzz target, which runs its parser and both parts on arbitrary input.
+pub fn render_fuzz_target(day: u8) -> String {
+    FUZZ_TEMPLATE.replace("{{day}}", &day.to_string())
+}
+
+/// Declares the day's fuzz target at the end of the fuzz crate's `Cargo.toml`.
+pub fn register_fuzz_target(manifest: &str, day: u8) -> String {
+    let mut manifest = manifest.trim_end().to_string();
+    manifest.push_str(&format!(
+        "\n\n[[bin]]\nname = \"day{0}\"\npath = \"fuzz_targets/day{0}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        day
+    ));
+
+    manifest
+}
+
+/// Declares the day's module in `lib.rs`, in the order rustfmt keeps module declarations in, and
+/// adds its solution to `DAYS` in calendar order.
+pub fn register(lib: &str, day: u8) -> Result<String, String> {
+    let lib = insert_entry(
+        lib,
+        day,
+        &format!("pub mod day{};\n", day),
+        |line| {
+            line.trim_end()
+                .strip_prefix("pub mod day")?
+                .strip_suffix(';')?
+                .parse()
+                .ok()
+        },
+        |day| format!("day{}", day),
+    )
+    .ok_or("lib.rs declares no day modules")??;
+
+    insert_entry(
+        &lib,
+        day,
+        &format!("    &day{0}::Day{0},\n", day),
+        |line| {
+            line.trim()
+                .strip_prefix("&day")?
+                .split_once("::")?
+                .0
+                .parse()
+                .ok()
+        },
+        |day| day,
+    )
+    .ok_or("lib.rs lists no days in DAYS")?
+}
+
+/// Inserts `entry` among the lines for which `registered` gives a day, before the first one with a
+/// larger `key`, or after the last one. `None` if there are no such lines.
+fn insert_entry<K: Ord>(
+    lib: &str,
+    day: u8,
+    entry: &str,
+    registered: impl Fn(&str) -> Option<u8>,
+    key: impl Fn(u8) -> K,
+) -> Option<Result<String, String>> {
+    let mut before = None;
+    let mut after = None;
+    let mut offset = 0;
+
+    for line in lib.split_inclusive('\n') {
+        if let Some(registered) = registered(line) {
+            if registered == day {
+                return Some(Err(format!("day {} is already registered in lib.rs", day)));
+            }
+
+            if before.is_none() && key(registered) > key(day) {
+                before = Some(offset);
+            }
+
+            after = Some(offset + line.len());
+        }
+
+        offset += line.len();
+    }
+
+    let offset = before.or(after)?;
+
+    Some(Ok(format!("{}{}{}", &lib[..offset], entry, &lib[offset..])))
+}
+
+/// Creates `src/dayN/mod.rs` and an empty `examples/2024/dayN.txt` below `root` and registers the
+/// day in `src/lib.rs`. The example has no answers in the example manifest yet, so the example
+/// test fails until the ones from the puzzle description are added. Nothing is written if the day
+/// exists already; an existing example is kept. When the fuzz crate is present the day also gets a
+/// fuzz target. If any write fails, the files written before it are restored. Returns the files
+/// that were created or changed.
+pub fn scaffold(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>, String> {
+    let directory = root.join("src").join(format!("day{}", day));
+    let module = directory.join("mod.rs");
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    let lib_path = root.join("src").join("lib.rs");
+    let lib = std::fs::read_to_string(&lib_path)
+        .map_err(|error| format!("cannot read {}: {}", lib_path.display(), error))?;
+    let lib = register(&lib, day)?;
+
+    let example = root
+        .join("examples")
+        .join("2024")
+        .join(format!("day{}.txt", day));
+
+    let fuzz = root.join("fuzz");
+    let fuzz_target = fuzz.join("fuzz_targets").join(format!("day{}.rs", day));
+    let fuzz_manifest_path = fuzz.join("Cargo.toml");
+
+    let mut files = vec![(module, render(day, title))];
+
+    if !example.exists() {
+        files.push((example, String::new()));
+    }
+
+    if !fuzz_target.exists() {
+        if let Ok(fuzz_manifest) = std::fs::read_to_string(&fuzz_manifest_path) {
+            files.push((fuzz_target, render_fuzz_target(day)));
+            files.push((
+                fuzz_manifest_path,
+                register_fuzz_target(&fuzz_manifest, day),
+            ));
+        }
+    }
+
+    files.push((lib_path, lib));
+
+    // Previous contents of every file written so far, `None` for the ones that are new
+    let mut written: Vec<(PathBuf, Option<String>)> = Vec::new();
+
+    for (path, contents) in files {
+        let previous = std::fs::read_to_string(&path).ok();
+
+        let result = std::fs::create_dir_all(path.parent().expect("files live in a directory"))
+            .and_then(|_| std::fs::write(&path, contents));
+
+        if let Err(error) = result {
+            for (path, previous) in written.iter().rev() {
+                match previous {
+                    Some(previous) => drop(std::fs::write(path, previous)),
+                    None => drop(std::fs::remove_file(path)),
+                }
+            }
+
+            // Only removes the day's directory when it is empty again
+            let _ = std::fs::remove_dir(&directory);
+
+            return Err(format!("cannot write {}: {}", path.display(), error));
+        }
+
+        written.push((path, previous));
+    }
+
+    Ok(written.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod day1;\npub mod day16;\npub mod day9;\n\npub const DAYS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day9::Day9,\n    &day16::Day16,\n];\n";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            "pub mod day1;\npub mod day16;\npub mod day6;\npub mod day9;\n\npub const DAYS: &[&dyn DynSolution] = &[\n    &day1::Day1,\n    &day6::Day6,\n    &day9::Day9,\n    &day16::Day16,\n];\n",
+            register(LIB, 6).unwrap()
+        );
+
+        let lib = register(LIB, 25).unwrap();
+        assert!(lib.starts_with("pub mod day1;\npub mod day16;\npub mod day25;\npub mod day9;\n"));
+        assert!(lib.ends_with("    &day16::Day16,\n    &day25::Day25,\n];\n"));
+
+        assert!(register(LIB, 16).is_err());
+        assert!(register("pub mod grid;\n", 6).is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        assert!(render(12, "Garden \"Groups\"")
+            .contains("const TITLE: &'static str = \"Garden \\\"Groups\\\"\";"));
+        assert!(render(12, "").contains("mod tests {"));
+
+        assert!(render_fuzz_target(12).contains("run::<Day12>(input)"));
+        assert_eq!(
+            "[workspace]\n\n[[bin]]\nname = \"day12\"\npath = \"fuzz_targets/day12.rs\"\ntest = false\ndoc = false\nbench = false\n",
+            register_fuzz_target("[workspace]\n", 12)
+        );
+
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert!(scaffold(root, 1, "Historian Hysteria").is_err());
+    }
+
+    /// A write that fails leaves the tree as it was, and a new example gets no answers.
+    #[test]
+    fn test_scaffold_files() {
+        let root = std::env::temp_dir().join(format!("scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("fuzz")).unwrap();
+        std::fs::write(root.join("src").join("lib.rs"), LIB).unwrap();
+        std::fs::write(root.join("fuzz").join("Cargo.toml"), "[workspace]\n").unwrap();
+
+        // A file where the fuzz targets directory should be
+        std::fs::write(root.join("fuzz").join("fuzz_targets"), "").unwrap();
+
+        assert!(scaffold(&root, 12, "Garden Groups").is_err());
+        assert!(!root.join("src").join("day12").exists());
+        assert!(!root
+            .join("examples")
+            .join("2024")
+            .join("day12.txt")
+            .exists());
+        assert_eq!(
+            LIB,
+            std::fs::read_to_string(root.join("src").join("lib.rs")).unwrap()
+        );
+
+        std::fs::remove_file(root.join("fuzz").join("fuzz_targets")).unwrap();
+        let written = scaffold(&root, 12, "Garden Groups").unwrap();
+
+        assert_eq!(5, written.len());
+        assert!(written.iter().all(|path| path.exists()));
+        assert!(!root
+            .join("examples")
+            .join("2024")
+            .join("manifest.txt")
+            .exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,452 @@
+use crate::stats::Counter;
+use fixedbitset::FixedBitSet;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// A search state that maps onto a dense index below the `state_count` given to a search, so all
+/// bookkeeping can live in flat vectors instead of hash maps.
+pub trait State: Copy {
+    fn index(&self) -> usize;
+}
+
+impl State for usize {
+    fn index(&self) -> usize {
+        *self
+    }
+}
+
+/// Breadth first search with buffers that are reused between runs, e.g. one run per trail head.
+pub struct Bfs<S> {
+    queue: VecDeque<S>,
+    distances: Vec<usize>,
+    reached: Vec<S>,
+}
+
+impl<S: State> Bfs<S> {
+    pub fn new(state_count: usize) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            distances: vec![usize::MAX; state_count],
+            reached: Vec::new(),
+        }
+    }
+
+    /// Explores everything reachable from the starts. Only the states reached by the previous run
+    /// are reset, so a run costs time proportional to its own reach.
+    pub fn run<I>(
+        &mut self,
+        starts: impl IntoIterator<Item = S>,
+        mut neighbours: impl FnMut(S) -> I,
+    ) where
+        I: IntoIterator<Item = S>,
+    {
+        for state in self.reached.drain(..) {
+            self.distances[state.index()] = usize::MAX;
+        }
+
+        self.queue.clear();
+
+        for start in starts {
+            if self.distances[start.index()] == usize::MAX {
+                self.distances[start.index()] = 0;
+                self.reached.push(start);
+                self.queue.push_back(start);
+            }
+        }
+
+        while let Some(state) = self.queue.pop_front() {
+            let distance = self.distances[state.index()];
+
+            for next in neighbours(state) {
+                if self.distances[next.index()] != usize::MAX {
+                    continue;
+                }
+
+                self.distances[next.index()] = distance + 1;
+                self.reached.push(next);
+                self.queue.push_back(next);
+            }
+        }
+    }
+
+    /// Number of steps from the nearest start, `None` if the last run didn't reach the state.
+    pub fn distance(&self, state: S) -> Option<usize> {
+        let distance = self.distances[state.index()];
+        (distance != usize::MAX).then_some(distance)
+    }
+
+    /// All states reached by the last run, in the order they were found.
+    pub fn reached(&self) -> &[S] {
+        &self.reached
+    }
+}
+
+const NO_EDGE: u32 = u32::MAX;
+
+/// Result of [`dijkstra`]: the distance to every settled state and, for each of them, all
+/// predecessors on a shortest path towards it.
+pub struct ShortestPaths<S> {
+    distances: Vec<usize>,
+    /// Head of the predecessor list of every state, an index into `edges`.
+    heads: Vec<u32>,
+    edges: Vec<(S, u32)>,
+    targets: Vec<S>,
+}
+
+impl<S: State> ShortestPaths<S> {
+    pub fn distance(&self, state: S) -> Option<usize> {
+        let distance = self.distances[state.index()];
+        (distance != usize::MAX).then_some(distance)
+    }
+
+    /// The cost of the cheapest path to a target, `None` if no target can be reached.
+    pub fn best(&self) -> Option<usize> {
+        self.targets
+            .first()
+            .and_then(|target| self.distance(*target))
+    }
+
+    /// All targets reached at the [best](Self::best) cost.
+    pub fn targets(&self) -> &[S] {
+        &self.targets
+    }
+
+    pub fn predecessors(&self, state: S) -> impl Iterator<Item = S> + '_ {
+        let mut edge = self.heads[state.index()];
+
+        std::iter::from_fn(move || {
+            let (predecessor, next) = *self.edges.get(edge as usize)?;
+            edge = next;
+            Some(predecessor)
+        })
+    }
+
+    /// Indices of all states that lie on at least one cheapest path to a target.
+    pub fn on_best_paths(&self) -> FixedBitSet {
+        let mut visited = FixedBitSet::with_capacity(self.distances.len());
+        let mut stack = self.targets.clone();
+
+        while let Some(state) = stack.pop() {
+            if visited.put(state.index()) {
+                continue;
+            }
+
+            stack.extend(self.predecessors(state));
+        }
+
+        visited
+    }
+}
+
+struct HeapEntry<S> {
+    cost: usize,
+    state: S,
+}
+
+impl<S: State> PartialEq for HeapEntry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: State> Eq for HeapEntry<S> {}
+
+impl<S: State> Ord for HeapEntry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Flip the ordering on costs to turn the max heap into a min heap, ties are broken on the
+        // state to keep `PartialEq` and `Ord` consistent.
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.state.index().cmp(&other.state.index()))
+    }
+}
+
+impl<S: State> PartialOrd for HeapEntry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Entries pushed onto the heap of [`dijkstra`].
+pub static HEAP_PUSHES: Counter = Counter::new("heap pushes");
+/// Entries popped from the heap of [`dijkstra`].
+pub static HEAP_POPS: Counter = Counter::new("heap pops");
+/// Popped entries that are skipped because their state was reached more cheaply in the meantime.
+pub static STALE_ENTRIES: Counter = Counter::new("stale entries");
+
+/// Dijkstra's algorithm from the starts until every target at the lowest cost has been settled.
+/// Unlike a plain shortest path search it keeps every predecessor that reaches a state at its
+/// lowest cost, so all cheapest paths can be recovered afterwards.
+pub fn dijkstra<S: State, I>(
+    starts: impl IntoIterator<Item = S>,
+    state_count: usize,
+    neighbours: impl FnMut(S) -> I,
+    is_target: impl FnMut(S) -> bool,
+) -> ShortestPaths<S>
+where
+    I: IntoIterator<Item = (S, usize)>,
+{
+    Dijkstra::new(starts, state_count, neighbours, is_target).into_paths()
+}
+
+/// A state taken off the frontier of [`Dijkstra`] at its lowest cost. Targets are not expanded
+/// any further.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Expansion<S> {
+    pub state: S,
+    pub cost: usize,
+}
+
+/// [`dijkstra`] one expansion at a time, e.g. to watch the frontier grow.
+pub struct Dijkstra<S, N, T> {
+    paths: ShortestPaths<S>,
+    heap: BinaryHeap<HeapEntry<S>>,
+    best: usize,
+    neighbours: N,
+    is_target: T,
+}
+
+impl<S: State, I, N, T> Dijkstra<S, N, T>
+where
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(S) -> I,
+    T: FnMut(S) -> bool,
+{
+    pub fn new(
+        starts: impl IntoIterator<Item = S>,
+        state_count: usize,
+        neighbours: N,
+        is_target: T,
+    ) -> Self {
+        let mut paths = ShortestPaths {
+            distances: vec![usize::MAX; state_count],
+            heads: vec![NO_EDGE; state_count],
+            edges: Vec::new(),
+            targets: Vec::new(),
+        };
+        let mut heap = BinaryHeap::new();
+
+        for start in starts {
+            paths.distances[start.index()] = 0;
+            HEAP_PUSHES.increment();
+            heap.push(HeapEntry {
+                cost: 0,
+                state: start,
+            });
+        }
+
+        Self {
+            paths,
+            heap,
+            best: usize::MAX,
+            neighbours,
+            is_target,
+        }
+    }
+
+    /// What has been found so far: distances are final for expanded states and upper bounds for
+    /// the ones still on the frontier.
+    pub fn paths(&self) -> &ShortestPaths<S> {
+        &self.paths
+    }
+
+    /// Finishes the search.
+    pub fn into_paths(mut self) -> ShortestPaths<S> {
+        self.by_ref().for_each(drop);
+        self.paths
+    }
+}
+
+impl<S: State, I, N, T> Iterator for Dijkstra<S, N, T>
+where
+    I: IntoIterator<Item = (S, usize)>,
+    N: FnMut(S) -> I,
+    T: FnMut(S) -> bool,
+{
+    type Item = Expansion<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let paths = &mut self.paths;
+
+        while let Some(HeapEntry { cost, state }) = self.heap.pop() {
+            HEAP_POPS.increment();
+
+            if cost > self.best {
+                self.heap.clear();
+                break;
+            }
+
+            if cost > paths.distances[state.index()] {
+                STALE_ENTRIES.increment();
+                continue;
+            }
+
+            if (self.is_target)(state) {
+                self.best = cost;
+                paths.targets.push(state);
+                return Some(Expansion { state, cost });
+            }
+
+            for (next, step) in (self.neighbours)(state) {
+                let next_cost = cost + step;
+                let next_index = next.index();
+
+                match next_cost.cmp(&paths.distances[next_index]) {
+                    Ordering::Less => {
+                        paths.distances[next_index] = next_cost;
+                        paths.edges.push((state, NO_EDGE));
+                        paths.heads[next_index] = (paths.edges.len() - 1) as u32;
+                        HEAP_PUSHES.increment();
+                        self.heap.push(HeapEntry {
+                            cost: next_cost,
+                            state: next,
+                        });
+                    }
+                    Ordering::Equal => {
+                        paths.edges.push((state, paths.heads[next_index]));
+                        paths.heads[next_index] = (paths.edges.len() - 1) as u32;
+                    }
+                    Ordering::Greater => {}
+                }
+            }
+
+            return Some(Expansion { state, cost });
+        }
+
+        None
+    }
+}
+
+/// Counts the distinct paths from a start to any target through an acyclic graph. Targets end a
+/// path, and the counts are memoised per state so they are shared between several starts.
+pub struct PathCounter<S> {
+    counts: Vec<Option<usize>>,
+    stack: Vec<(S, bool)>,
+}
+
+impl<S: State> PathCounter<S> {
+    pub fn new(state_count: usize) -> Self {
+        Self {
+            counts: vec![None; state_count],
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn count<I>(
+        &mut self,
+        start: S,
+        mut neighbours: impl FnMut(S) -> I,
+        mut is_target: impl FnMut(S) -> bool,
+    ) -> usize
+    where
+        I: IntoIterator<Item = S>,
+    {
+        self.stack.push((start, false));
+
+        while let Some((state, expanded)) = self.stack.pop() {
+            if self.counts[state.index()].is_some() {
+                continue;
+            }
+
+            if is_target(state) {
+                self.counts[state.index()] = Some(1);
+                continue;
+            }
+
+            if expanded {
+                let count = neighbours(state)
+                    .into_iter()
+                    .map(|next| {
+                        self.counts[next.index()].expect("path counting requires an acyclic graph")
+                    })
+                    .sum();
+
+                self.counts[state.index()] = Some(count);
+                continue;
+            }
+
+            self.stack.push((state, true));
+
+            for next in neighbours(state) {
+                if self.counts[next.index()].is_none() {
+                    self.stack.push((next, false));
+                }
+            }
+        }
+
+        self.counts[start.index()].unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small diamond shaped graph: 0 -> {1, 2} -> 3 -> 4, plus an expensive shortcut 0 -> 4.
+    fn neighbours(state: usize) -> Vec<(usize, usize)> {
+        match state {
+            0 => vec![(1, 1), (2, 1), (4, 5)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let mut bfs = Bfs::new(5);
+        bfs.run([0], |state| {
+            neighbours(state).into_iter().map(|(next, _)| next)
+        });
+
+        assert_eq!(Some(1), bfs.distance(4));
+        assert_eq!(Some(2), bfs.distance(3));
+        assert_eq!(5, bfs.reached().len());
+
+        bfs.run([3], |state| {
+            neighbours(state).into_iter().map(|(next, _)| next)
+        });
+        assert_eq!(None, bfs.distance(0));
+        assert_eq!(&[3, 4], bfs.reached());
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra([0], 5, neighbours, |state| state == 4);
+
+        assert_eq!(Some(3), paths.best());
+        assert_eq!(&[4], paths.targets());
+
+        let mut predecessors = paths.predecessors(3).collect::<Vec<_>>();
+        predecessors.sort();
+        assert_eq!(vec![1, 2], predecessors);
+        assert_eq!(5, paths.on_best_paths().count_ones(..));
+    }
+
+    #[test]
+    fn test_dijkstra_expansions() {
+        let mut search = Dijkstra::new([0], 5, neighbours, |state| state == 4);
+        let costs = search
+            .by_ref()
+            .map(|expansion| (expansion.state, expansion.cost))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![(0, 0), (2, 1), (1, 1), (3, 2), (4, 3)], costs);
+        assert_eq!(Some(3), search.paths().best());
+        assert_eq!(5, search.into_paths().on_best_paths().count_ones(..));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let mut counter = PathCounter::new(5);
+        let count = counter.count(
+            0,
+            |state| neighbours(state).into_iter().map(|(next, _)| next),
+            |state| state == 4,
+        );
+
+        assert_eq!(3, count);
+        assert_eq!(1, counter.count(3, |_| Vec::new(), |state| state == 4));
+    }
+}
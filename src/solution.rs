@@ -0,0 +1,187 @@
+use crate::generate::{Rng, Size};
+use crate::parse::normalize;
+use crate::render::Picture;
+use crate::stats::Counter;
+use crate::{Answer, ParseError};
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// One of the two parts of a puzzle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// `1` or `2`, as used on the website.
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One | Part::Two => write!(f, "Part {}", self.number()),
+        }
+    }
+}
+
+/// Why a part has no answer even though its input parsed, e.g. a maze without a path to its end.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SolveError {
+    pub day: u8,
+    pub reason: &'static str,
+}
+
+impl SolveError {
+    pub const fn new(day: u8, reason: &'static str) -> Self {
+        Self { day, reason }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}: {}", self.day, self.reason)
+    }
+}
+
+impl Error for SolveError {}
+
+/// A single puzzle of the calendar: how to parse its input and how to solve each part.
+pub trait Solution {
+    /// Day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// Title of the puzzle as shown on the website.
+    const TITLE: &'static str;
+
+    /// Example inputs from the puzzle description, relative to the crate root.
+    const EXAMPLES: &'static [&'static str];
+
+    /// Counters the solvers bump, reported for every part when the `stats` feature is enabled.
+    const COUNTERS: &'static [&'static Counter] = &[];
+
+    /// Whether [`Solution::part2`] gives an answer, so callers can skip it without solving.
+    /// Days without a second part (i.e. day 25) set it to `false`.
+    const HAS_PART2: bool = true;
+
+    /// Shared between threads when parts run in parallel.
+    type Input: Send + Sync + 'static;
+    /// Any type that converts into an [`Answer`], usually the integer type the day computes in.
+    type Output: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError>;
+
+    /// Days without a second part (i.e. day 25) keep the default.
+    fn part2(_input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        None
+    }
+
+    /// A random but valid puzzle input of about the given size, to benchmark at scale. Days without
+    /// a generator keep the default.
+    fn generate(_rng: &mut Rng, _size: &Size) -> Option<String> {
+        None
+    }
+
+    /// A picture of the parsed input with what the solvers found drawn over it, for debugging with
+    /// `--render`. Days without one keep the default.
+    fn render(_input: &Self::Input) -> Option<Picture> {
+        None
+    }
+}
+
+/// Object safe counterpart of [`Solution`] so that days with different input and output types
+/// can live together in [`crate::DAYS`].
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn examples(&self) -> &'static [&'static str];
+
+    fn counters(&self) -> &'static [&'static Counter];
+
+    fn has_part2(&self) -> bool;
+
+    /// Parses the input once it has been [normalized](normalize), so every day accepts files
+    /// saved with Windows line endings or a byte order mark.
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, ParseError>;
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer, SolveError>;
+
+    fn part2(&self, input: &dyn Any) -> Option<Result<Answer, SolveError>>;
+
+    /// Generates an input from the seed, the same seed and size always give the same input.
+    fn generate(&self, seed: u64, size: &Size) -> Option<String>;
+
+    fn render(&self, input: &dyn Any) -> Option<Picture>;
+
+    /// Parses the raw puzzle input and solves one part of it, `None` if the day has no such part.
+    /// Fails with the [`ParseError`] or [`SolveError`] of the day.
+    fn solve(&self, input: &str, part: Part) -> Result<Option<Answer>, Box<dyn Error>> {
+        let input = self.parse(input)?;
+
+        let answer = match part {
+            Part::One => Some(self.part1(&*input)),
+            Part::Two => self.part2(&*input),
+        };
+
+        Ok(answer.transpose()?)
+    }
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        S::EXAMPLES
+    }
+
+    fn counters(&self) -> &'static [&'static Counter] {
+        S::COUNTERS
+    }
+
+    fn has_part2(&self) -> bool {
+        S::HAS_PART2
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, ParseError> {
+        Ok(Box::new(S::parse(&normalize(input))?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<Answer, SolveError> {
+        S::part1(downcast::<S>(input)).map(Into::into)
+    }
+
+    fn part2(&self, input: &dyn Any) -> Option<Result<Answer, SolveError>> {
+        S::part2(downcast::<S>(input)).map(|answer| answer.map(Into::into))
+    }
+
+    fn generate(&self, seed: u64, size: &Size) -> Option<String> {
+        S::generate(&mut Rng::with_seed(seed), size)
+    }
+
+    fn render(&self, input: &dyn Any) -> Option<Picture> {
+        S::render(downcast::<S>(input))
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .unwrap_or_else(|| panic!("Input was not parsed by day {}", S::DAY))
+}
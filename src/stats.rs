@@ -0,0 +1,96 @@
+#[cfg(feature = "stats")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of times something happens inside a solver, such as a cache hit, to reason about
+/// algorithmic changes without a profiler. Counting compiles to nothing unless the `stats` feature
+/// is enabled.
+///
+/// Counters are shared by every thread, so the counts of parts that run at the same time (e.g.
+/// with `--parallel`) get mixed up.
+pub struct Counter {
+    name: &'static str,
+    #[cfg(feature = "stats")]
+    count: AtomicU64,
+}
+
+impl Counter {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            #[cfg(feature = "stats")]
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn increment(&self) {
+        self.add(1);
+    }
+
+    #[inline(always)]
+    pub fn add(&self, _amount: u64) {
+        #[cfg(feature = "stats")]
+        self.count.fetch_add(_amount, Ordering::Relaxed);
+    }
+
+    /// Count since the last reset.
+    #[cfg(feature = "stats")]
+    pub fn get(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Always 0 without the `stats` feature.
+    #[cfg(not(feature = "stats"))]
+    pub fn get(&self) -> u64 {
+        0
+    }
+
+    pub fn reset(&self) {
+        #[cfg(feature = "stats")]
+        self.count.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Resets the counters, e.g. before solving a part.
+pub fn reset(counters: &[&Counter]) {
+    counters.iter().for_each(|counter| counter.reset());
+}
+
+/// Names and counts of the counters since they were reset, empty without the `stats` feature.
+pub fn snapshot(counters: &[&Counter]) -> Vec<(&'static str, u64)> {
+    if !cfg!(feature = "stats") {
+        return Vec::new();
+    }
+
+    counters
+        .iter()
+        .map(|counter| (counter.name(), counter.get()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static COUNTER: Counter = Counter::new("events");
+
+    #[test]
+    fn test_counter() {
+        COUNTER.increment();
+        COUNTER.add(2);
+
+        if cfg!(feature = "stats") {
+            assert_eq!(vec![("events", 3)], snapshot(&[&COUNTER]));
+        } else {
+            assert_eq!(0, COUNTER.get());
+            assert!(snapshot(&[&COUNTER]).is_empty());
+        }
+
+        reset(&[&COUNTER]);
+        assert_eq!(0, COUNTER.get());
+    }
+}
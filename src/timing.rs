@@ -0,0 +1,259 @@
+use crate::runner::{run, Input, Status};
+use crate::{DynSolution, Part};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The step of a day that is timed on its own.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{}", part.number()),
+        }
+    }
+}
+
+/// Summary of the samples taken of a single phase.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    /// 95th percentile, using the nearest rank.
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises the samples, `None` if there are none.
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        samples.sort_unstable();
+
+        let rank = |percentile: usize| {
+            let rank = (samples.len() * percentile).div_ceil(100);
+            samples[rank.saturating_sub(1)]
+        };
+
+        Some(Self {
+            min: *samples.first()?,
+            median: rank(50),
+            p95: rank(95),
+            max: *samples.last()?,
+        })
+    }
+}
+
+/// Timings of every phase of a day on a single input.
+#[derive(Debug)]
+pub struct Timing {
+    pub day: u8,
+    pub title: &'static str,
+    pub input: String,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+/// Parses the input and solves the parts `warmup` times without measuring, then `repeat` times
+/// while recording every phase separately. The day is run once beforehand, so that inputs it
+/// rejects and parts that panic or aren't implemented are reported instead of timed.
+pub fn measure(
+    solution: &dyn DynSolution,
+    input: &Input,
+    parts: &[Part],
+    warmup: usize,
+    repeat: usize,
+) -> Result<Timing, (Status, String)> {
+    let checked = run(solution, input, parts);
+    if checked.status != Status::Ok {
+        return Err((checked.status, checked.message.unwrap_or_default()));
+    }
+
+    let parts = checked
+        .parts
+        .iter()
+        .filter(|part| part.status == Status::Ok)
+        .map(|part| part.part)
+        .collect::<Vec<_>>();
+
+    let mut parse_samples = Vec::with_capacity(repeat);
+    let mut solve_samples = vec![Vec::with_capacity(repeat); parts.len()];
+
+    for iteration in 0..warmup + repeat {
+        let start = Instant::now();
+        let parsed =
+            black_box(solution.parse(black_box(&input.text))).expect("the input parsed before");
+        let parse_time = start.elapsed();
+
+        for (index, part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            // The answer is never used, hide that from the optimiser so it cannot skip the work
+            black_box(match part {
+                Part::One => Some(solution.part1(black_box(&*parsed))),
+                Part::Two => solution.part2(black_box(&*parsed)),
+            });
+            let solve_time = start.elapsed();
+
+            if iteration >= warmup {
+                solve_samples[index].push(solve_time);
+            }
+        }
+
+        if iteration >= warmup {
+            parse_samples.push(parse_time);
+        }
+    }
+
+    let phases = std::iter::once((Phase::Parse, parse_samples))
+        .chain(parts.into_iter().map(Phase::Solve).zip(solve_samples))
+        .filter_map(|(phase, mut samples)| Some((phase, Stats::from_samples(&mut samples)?)))
+        .collect();
+
+    Ok(Timing {
+        day: solution.day(),
+        title: solution.title(),
+        input: input.label.clone(),
+        phases,
+    })
+}
+
+/// A single phase of a saved timing file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Record {
+    day: u8,
+    input: String,
+    phase: String,
+    min_ns: u64,
+    median_ns: u64,
+    p95_ns: u64,
+    max_ns: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Document {
+    year: u16,
+    timings: Vec<Record>,
+}
+
+/// Previously saved timings to compare a new measurement against.
+#[derive(Default)]
+pub struct Baseline {
+    records: Vec<Record>,
+}
+
+impl Baseline {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        let document: Document = serde_json::from_str(json)?;
+
+        Ok(Self {
+            records: document.timings,
+        })
+    }
+
+    /// Median of the phase when the baseline was saved.
+    pub fn median(&self, timing: &Timing, phase: Phase) -> Option<Duration> {
+        let phase = phase.to_string();
+
+        self.records
+            .iter()
+            .find(|record| {
+                record.day == timing.day && record.input == timing.input && record.phase == phase
+            })
+            .map(|record| Duration::from_nanos(record.median_ns))
+    }
+}
+
+/// Change from `before` to `after` in percent, positive if it got slower.
+pub fn delta(before: Duration, after: Duration) -> f64 {
+    (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0
+}
+
+/// Renders the timings in the format read by [`Baseline::parse`]:
+///
+/// ```json
+/// {"year": 2024, "timings": [{"day": 1, "input": "inputs/day1.txt", "phase": "parse",
+///   "min_ns": 5120, "median_ns": 5300, "p95_ns": 6010, "max_ns": 9800}]}
+/// ```
+pub fn to_json(timings: &[Timing]) -> String {
+    let nanos = |duration: Duration| duration.as_nanos().try_into().unwrap_or(u64::MAX);
+
+    let records = timings
+        .iter()
+        .flat_map(|timing| {
+            timing.phases.iter().map(|(phase, stats)| Record {
+                day: timing.day,
+                input: timing.input.clone(),
+                phase: phase.to_string(),
+                min_ns: nanos(stats.min),
+                median_ns: nanos(stats.median),
+                p95_ns: nanos(stats.p95),
+                max_ns: nanos(stats.max),
+            })
+        })
+        .collect();
+
+    let document = Document {
+        year: 2024,
+        timings: records,
+    };
+
+    serde_json::to_string(&document).expect("records always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find_day;
+
+    #[test]
+    fn test_stats() {
+        let mut samples = (1..=20)
+            .rev()
+            .map(Duration::from_millis)
+            .collect::<Vec<_>>();
+        let stats = Stats::from_samples(&mut samples).unwrap();
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(10), stats.median);
+        assert_eq!(Duration::from_millis(19), stats.p95);
+        assert_eq!(Duration::from_millis(20), stats.max);
+        assert_eq!(None, Stats::from_samples(&mut []));
+    }
+
+    #[test]
+    fn test_measure() {
+        let input = Input {
+            label: "test".to_string(),
+            text: "#####\n.....\n.....\n.....\n.....\n.....\n.....\n".to_string(),
+        };
+
+        // Day 25 has no second part, so it has no timing either
+        let timing = measure(find_day(25).unwrap(), &input, &[Part::One, Part::Two], 1, 5).unwrap();
+        let phases = timing
+            .phases
+            .iter()
+            .map(|(phase, _)| *phase)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Phase::Parse, Phase::Solve(Part::One)], phases);
+
+        let baseline = Baseline::parse(&to_json(&[timing])).unwrap();
+        let timing = measure(find_day(25).unwrap(), &input, &[Part::One], 0, 1).unwrap();
+        assert!(baseline.median(&timing, Phase::Parse).is_some());
+        assert!(baseline.median(&timing, Phase::Solve(Part::Two)).is_none());
+
+        let error = measure(find_day(1).unwrap(), &input, &[Part::One], 0, 1).unwrap_err();
+        assert_eq!(Status::Error, error.0);
+    }
+
+    #[test]
+    fn test_delta() {
+        let before = Duration::from_millis(200);
+        assert!((delta(before, Duration::from_millis(220)) - 10.0).abs() < 1e-9);
+        assert!((delta(before, Duration::from_millis(100)) + 50.0).abs() < 1e-9);
+    }
+}
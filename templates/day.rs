@@ -0,0 +1,60 @@
+use crate::generate::{Rng, Size};
+use crate::parse::lines;
+use crate::{ParseError, Solution, SolveError};
+
+type Output = usize;
+type Input = Vec<String>;
+
+pub fn parse_input(input: &str) -> Result<Input, ParseError> {
+    Ok(lines({{day}}, input).map(|line| line.text.to_string()).collect())
+}
+
+pub fn part1(_input: &Input) -> Output {
+    0
+}
+
+pub fn part2(_input: &Input) -> Output {
+    0
+}
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    const DAY: u8 = {{day}};
+    const TITLE: &'static str = {{title}};
+    const EXAMPLES: &'static [&'static str] = &["examples/2024/day{{day}}.txt"];
+
+    type Input = Input;
+    type Output = Output;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, SolveError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Result<Self::Output, SolveError>> {
+        Some(Ok(part2(input)))
+    }
+
+    /// No generator yet, which keeps the day out of the scaling benchmarks and the tests on
+    /// generated inputs. Write a `generate_input` like the other days have once the parts work.
+    fn generate(_rng: &mut Rng, _size: &Size) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../examples/2024/day{{day}}.txt");
+
+    /// The manifest checks the answers on the example, test the steps towards them here.
+    #[test]
+    fn test_parse() {
+        assert!(parse_input(TEST_INPUT).is_ok());
+    }
+}
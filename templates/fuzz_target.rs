@@ -0,0 +1,6 @@
+#![no_main]
+
+use advent_of_code_2024::day{{day}}::Day{{day}};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| advent_of_code_2024_fuzz::run::<Day{{day}}>(input));